@@ -0,0 +1,14 @@
+-- Live focus timer state owned by the backend (at most one per user)
+CREATE TABLE IF NOT EXISTS active_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL CHECK(status IN ('running', 'paused')),
+    started_at TEXT NOT NULL,
+    resumed_at TEXT,
+    accumulated_seconds INTEGER NOT NULL DEFAULT 0,
+    category TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
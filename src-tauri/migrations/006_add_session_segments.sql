@@ -0,0 +1,24 @@
+-- Focused stretches within a session; gaps between them are pauses
+CREATE TABLE IF NOT EXISTS session_segments (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    FOREIGN KEY (session_id) REFERENCES focus_sessions(id) ON DELETE CASCADE
+);
+
+-- Finished stretches of a timer that is still in progress
+CREATE TABLE IF NOT EXISTS active_session_segments (
+    id TEXT PRIMARY KEY NOT NULL,
+    active_session_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    FOREIGN KEY (active_session_id) REFERENCES active_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_segments_session ON session_segments(session_id, position);
+CREATE INDEX IF NOT EXISTS idx_active_session_segments_session ON active_session_segments(active_session_id, position);
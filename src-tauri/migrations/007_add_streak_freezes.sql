@@ -0,0 +1,14 @@
+-- Streak freeze tokens; a used token covers one missed day
+CREATE TABLE IF NOT EXISTS streak_freezes (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    source TEXT NOT NULL CHECK(source IN ('granted', 'earned')),
+    earned_for TEXT,
+    granted_at TEXT NOT NULL,
+    used_on TEXT,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(user_id, earned_for),
+    UNIQUE(user_id, used_on)
+);
+
+CREATE INDEX IF NOT EXISTS idx_streak_freezes_user ON streak_freezes(user_id);
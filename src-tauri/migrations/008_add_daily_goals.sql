@@ -0,0 +1,12 @@
+-- Daily focus targets; weekday NULL is the default, 0-6 (Sunday-Saturday) override it
+CREATE TABLE IF NOT EXISTS daily_goals (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    weekday INTEGER CHECK(weekday BETWEEN 0 AND 6),
+    target_minutes INTEGER NOT NULL CHECK(target_minutes > 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_daily_goals_user_weekday ON daily_goals(user_id, IFNULL(weekday, -1));
@@ -0,0 +1,13 @@
+-- Weekly and monthly focus targets, optionally limited to one category
+CREATE TABLE IF NOT EXISTS period_goals (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    period TEXT NOT NULL CHECK(period IN ('week', 'month')),
+    target_seconds INTEGER NOT NULL CHECK(target_seconds > 0),
+    category TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_period_goals_user ON period_goals(user_id);
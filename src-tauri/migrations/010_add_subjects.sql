@@ -0,0 +1,62 @@
+-- Subjects replace free-form focus session categories
+CREATE TABLE IF NOT EXISTS subjects (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT,
+    icon TEXT,
+    archived INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_subjects_user_name ON subjects(user_id, name COLLATE NOCASE);
+
+ALTER TABLE focus_sessions ADD COLUMN subject_id TEXT REFERENCES subjects(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_subject ON focus_sessions(subject_id);
+
+-- One subject per user for categories that differ only in case or surrounding
+-- whitespace, named after the earliest spelling used
+INSERT INTO subjects (id, user_id, name)
+SELECT
+    lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' ||
+    substr(lower(hex(randomblob(2))), 2) || '-' ||
+    substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' ||
+    lower(hex(randomblob(6))),
+    user_id,
+    name
+FROM (
+    SELECT
+        user_id,
+        TRIM(category) AS name,
+        ROW_NUMBER() OVER (
+            PARTITION BY user_id, LOWER(TRIM(category))
+            ORDER BY started_at ASC
+        ) AS spelling_rank
+    FROM focus_sessions
+    WHERE category IS NOT NULL AND TRIM(category) <> ''
+)
+WHERE spelling_rank = 1;
+
+UPDATE focus_sessions
+SET subject_id = (
+    SELECT s.id FROM subjects s
+    WHERE s.user_id = focus_sessions.user_id AND s.name = TRIM(focus_sessions.category) COLLATE NOCASE
+)
+WHERE category IS NOT NULL AND TRIM(category) <> '';
+
+-- Keep the category text in step with the subject name (blank categories become NULL)
+UPDATE focus_sessions
+SET category = (SELECT name FROM subjects WHERE subjects.id = focus_sessions.subject_id);
+
+UPDATE period_goals
+SET category = (
+    SELECT s.name FROM subjects s
+    WHERE s.user_id = period_goals.user_id AND s.name = TRIM(period_goals.category) COLLATE NOCASE
+)
+WHERE EXISTS (
+    SELECT 1 FROM subjects s
+    WHERE s.user_id = period_goals.user_id AND s.name = TRIM(period_goals.category) COLLATE NOCASE
+);
@@ -0,0 +1,6 @@
+-- Optional link from focus sessions (finished and live) to the task they were spent on
+ALTER TABLE focus_sessions ADD COLUMN task_id TEXT REFERENCES tasks(id) ON DELETE SET NULL;
+
+ALTER TABLE active_sessions ADD COLUMN task_id TEXT REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_task ON focus_sessions(task_id);
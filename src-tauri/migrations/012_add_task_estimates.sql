@@ -0,0 +1,2 @@
+-- Planned effort for a task, compared against focus time logged against it
+ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER CHECK(estimated_minutes > 0);
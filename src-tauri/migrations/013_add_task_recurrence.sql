@@ -0,0 +1,18 @@
+-- RRULE-style recurrence on tasks; the task's date is the first day of the series
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
+
+-- Per-occurrence state of recurring tasks (completion, skipped or moved instances)
+CREATE TABLE IF NOT EXISTS task_occurrences (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL,
+    occurrence_date TEXT NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    skipped INTEGER NOT NULL DEFAULT 0,
+    date TEXT,
+    start_time TEXT,
+    end_time TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE(task_id, occurrence_date)
+);
@@ -0,0 +1,16 @@
+-- Ordered checklist entries inside a task
+CREATE TABLE IF NOT EXISTS task_items (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    position INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_items_task_position ON task_items(task_id, position);
+
+-- When set, a task with checklist items is complete exactly when all its items are
+ALTER TABLE tasks ADD COLUMN auto_complete INTEGER NOT NULL DEFAULT 0;
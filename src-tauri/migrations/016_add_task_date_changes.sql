@@ -0,0 +1,12 @@
+-- History of task date moves, so postponed work can be reported
+CREATE TABLE IF NOT EXISTS task_date_changes (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL,
+    from_date TEXT NOT NULL,
+    to_date TEXT NOT NULL,
+    reason TEXT NOT NULL CHECK(reason IN ('rollover', 'reschedule')),
+    changed_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_date_changes_task ON task_date_changes(task_id, changed_at);
@@ -0,0 +1,17 @@
+-- Delivery state of task reminders; one row per task and due time, so a
+-- reminder is not repeated after a restart but fires again if the task moves
+CREATE TABLE IF NOT EXISTS task_reminders (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL,
+    occurrence_date TEXT,
+    due_at TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('delivered', 'snoozed', 'dismissed')),
+    delivered_at TEXT,
+    snoozed_until TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE(task_id, due_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_reminders_status ON task_reminders(status, snoozed_until);
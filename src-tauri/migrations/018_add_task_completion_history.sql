@@ -0,0 +1,20 @@
+-- When a task or occurrence was completed (RFC3339); NULL while open. Tasks
+-- completed before this was tracked keep NULL and count on their own date.
+ALTER TABLE tasks ADD COLUMN completed_at TEXT;
+ALTER TABLE task_occurrences ADD COLUMN completed_at TEXT;
+
+-- Log of task changes. Not tied to tasks by a foreign key so the history
+-- (and the title at the time) survives deleting a task.
+CREATE TABLE IF NOT EXISTS task_events (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    occurrence_date TEXT,
+    event_type TEXT NOT NULL CHECK(event_type IN ('created', 'updated', 'completed', 'reopened', 'deleted')),
+    title TEXT NOT NULL,
+    occurred_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_task ON task_events(task_id, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_task_events_user ON task_events(user_id, occurred_at);
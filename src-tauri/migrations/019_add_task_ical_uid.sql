@@ -0,0 +1,5 @@
+-- UID of the calendar entry a task was imported from, so importing the same
+-- calendar file again updates those tasks instead of duplicating them
+ALTER TABLE tasks ADD COLUMN ical_uid TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_ical_uid ON tasks(user_id, ical_uid) WHERE ical_uid IS NOT NULL;
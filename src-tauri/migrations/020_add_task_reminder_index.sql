@@ -0,0 +1,2 @@
+-- The reminder scheduler looks up Reminder/Event tasks by day on every tick
+CREATE INDEX IF NOT EXISTS idx_tasks_category_date ON tasks(category, date);
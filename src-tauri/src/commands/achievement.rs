@@ -1,5 +1,10 @@
+use crate::commands::clock::UserClock;
+use crate::commands::session::parse_session_timestamp;
+use crate::commands::streak::{active_dates, frozen_dates, longest_streak};
+use crate::commands::task::count_completed_tasks;
 use crate::db::Database;
 use crate::models::{Achievement, AchievementInfo, AchievementType};
+use chrono::Timelike;
 use rusqlite::params;
 use tauri::State;
 use uuid::Uuid;
@@ -138,6 +143,52 @@ pub fn check_and_unlock_achievements(
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
     let mut newly_unlocked = Vec::new();
 
+    for (achievement_type, condition) in evaluate_achievements(&conn, &user_id)? {
+        if condition && !is_achievement_unlocked(&conn, &user_id, &achievement_type)? {
+            if let Ok(achievement) = unlock_achievement_internal(&conn, &user_id, achievement_type) {
+                newly_unlocked.push(achievement);
+            }
+        }
+    }
+
+    Ok(newly_unlocked)
+}
+
+/// Remove session-based achievements whose conditions no longer hold
+/// (e.g. after a focus session was edited or deleted). Nothing is revoked
+/// unless every condition could be evaluated.
+pub(crate) fn revoke_unearned_achievements(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<AchievementType>, String> {
+    let mut revoked = Vec::new();
+
+    for (achievement_type, condition) in evaluate_achievements(conn, user_id)? {
+        // Task achievements are not affected by session edits
+        if condition || achievement_type == AchievementType::TaskMaster {
+            continue;
+        }
+
+        let removed = conn
+            .execute(
+                "DELETE FROM achievements WHERE user_id = ?1 AND achievement_type = ?2",
+                params![user_id, achievement_type.as_str()],
+            )
+            .map_err(|e| e.to_string())?;
+
+        if removed > 0 {
+            revoked.push(achievement_type);
+        }
+    }
+
+    Ok(revoked)
+}
+
+/// Evaluate every achievement condition against the user's current data
+fn evaluate_achievements(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<(AchievementType, bool)>, String> {
     // Get user stats
     let (total_focus_time, total_sessions): (i64, i64) = conn
         .query_row(
@@ -145,18 +196,12 @@ pub fn check_and_unlock_achievements(
             params![user_id],
             |row| Ok((row.get(0)?, row.get(1)?)),
         )
-        .unwrap_or((0, 0));
+        .map_err(|e| e.to_string())?;
 
-    let tasks_completed: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM tasks WHERE user_id = ?1 AND completed = 1",
-            params![user_id],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
+    let tasks_completed = count_completed_tasks(conn, user_id)?;
 
-    // Get longest streak
-    let longest_streak = calculate_longest_streak(&conn, &user_id).unwrap_or(0);
+    // Get longest streak (frozen days keep a streak alive)
+    let longest_streak = longest_streak(&active_dates(conn, user_id)?, &frozen_dates(conn, user_id)?);
 
     // Get max session duration
     let max_session_duration: i64 = conn
@@ -165,14 +210,14 @@ pub fn check_and_unlock_achievements(
             params![user_id],
             |row| row.get(0),
         )
-        .unwrap_or(0);
+        .map_err(|e| e.to_string())?;
 
     // Time-based achievements
-    let has_early_bird = has_early_bird_session(&conn, &user_id).unwrap_or(false);
-    let has_night_owl = has_night_owl_session(&conn, &user_id).unwrap_or(false);
+    let has_early_bird = has_early_bird_session(conn, user_id)?;
+    let has_night_owl = has_night_owl_session(conn, user_id)?;
 
     // Check each achievement
-    let checks: Vec<(AchievementType, bool)> = vec![
+    Ok(vec![
         (AchievementType::FirstSession, total_sessions >= 1),
         (AchievementType::HourMaster, max_session_duration >= 3600),
         (AchievementType::StreakWeek, longest_streak >= 7),
@@ -183,17 +228,7 @@ pub fn check_and_unlock_achievements(
         (AchievementType::EarlyBird, has_early_bird),
         (AchievementType::NightOwl, has_night_owl),
         (AchievementType::TaskMaster, tasks_completed >= 50),
-    ];
-
-    for (achievement_type, condition) in checks {
-        if condition && !is_achievement_unlocked(&conn, &user_id, &achievement_type)? {
-            if let Ok(achievement) = unlock_achievement_internal(&conn, &user_id, achievement_type) {
-                newly_unlocked.push(achievement);
-            }
-        }
-    }
-
-    Ok(newly_unlocked)
+    ])
 }
 
 /// Check if an achievement is already unlocked
@@ -212,29 +247,40 @@ fn is_achievement_unlocked(
 }
 
 fn has_early_bird_session(conn: &rusqlite::Connection, user_id: &str) -> Result<bool, String> {
-    conn.query_row(
-        "SELECT EXISTS(
-            SELECT 1 FROM focus_sessions
-            WHERE user_id = ?1
-              AND CAST(strftime('%H', started_at, 'localtime') AS INTEGER) < 6
-        )",
-        params![user_id],
-        |row| row.get(0),
-    )
-    .map_err(|e| e.to_string())
+    let hours = session_local_hours(conn, user_id, "started_at")?;
+    Ok(hours.into_iter().any(|hour| hour < 6))
 }
 
 fn has_night_owl_session(conn: &rusqlite::Connection, user_id: &str) -> Result<bool, String> {
-    conn.query_row(
-        "SELECT EXISTS(
-            SELECT 1 FROM focus_sessions
-            WHERE user_id = ?1
-              AND CAST(strftime('%H', ended_at, 'localtime') AS INTEGER) >= 23
-        )",
-        params![user_id],
-        |row| row.get(0),
-    )
-    .map_err(|e| e.to_string())
+    let hours = session_local_hours(conn, user_id, "ended_at")?;
+    Ok(hours.into_iter().any(|hour| hour >= 23))
+}
+
+/// Hour of day, in the user's time zone, of a session timestamp column
+fn session_local_hours(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    column: &str,
+) -> Result<Vec<u32>, String> {
+    let clock = UserClock::load(conn, user_id)?;
+
+    let query = format!("SELECT {} FROM focus_sessions WHERE user_id = ?1", column);
+    let mut stmt = conn.prepare(&query).map_err(|e| e.to_string())?;
+
+    let timestamps: Vec<String> = stmt
+        .query_map(params![user_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Unreadable timestamps count toward no hour
+    let hours = timestamps
+        .iter()
+        .filter_map(|value| parse_session_timestamp(value).ok())
+        .map(|parsed| clock.local_datetime(parsed).hour())
+        .collect();
+
+    Ok(hours)
 }
 
 /// Internal function to unlock an achievement
@@ -261,47 +307,3 @@ fn unlock_achievement_internal(
         metadata: None,
     })
 }
-
-/// Calculate longest streak (reused logic from session.rs)
-fn calculate_longest_streak(conn: &rusqlite::Connection, user_id: &str) -> Result<i64, String> {
-    let dates: Vec<String> = {
-        let mut stmt = conn
-            .prepare(
-                "SELECT DISTINCT date FROM daily_stats 
-                 WHERE user_id = ?1 AND total_focus_seconds > 0 
-                 ORDER BY date ASC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let rows = stmt.query_map(params![user_id], |row| row.get(0))
-            .map_err(|e| e.to_string())?;
-        
-        rows.collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_string())?
-    };
-
-    if dates.is_empty() {
-        return Ok(0);
-    }
-
-    let mut longest = 1i64;
-    let mut current = 1i64;
-
-    for i in 1..dates.len() {
-        let prev_date =
-            chrono::NaiveDate::parse_from_str(&dates[i - 1], "%Y-%m-%d").map_err(|e| e.to_string())?;
-        let curr_date =
-            chrono::NaiveDate::parse_from_str(&dates[i], "%Y-%m-%d").map_err(|e| e.to_string())?;
-
-        if curr_date - prev_date == chrono::Duration::days(1) {
-            current += 1;
-            if current > longest {
-                longest = current;
-            }
-        } else {
-            current = 1;
-        }
-    }
-
-    Ok(longest)
-}
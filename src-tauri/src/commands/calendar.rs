@@ -0,0 +1,759 @@
+use crate::commands::clock::{resolve_local, UserClock};
+use crate::commands::ical::{
+    escape_text, parse_calendar, parse_duration, split_text_list, CalendarWriter, Component, DateValue,
+};
+use crate::commands::recurrence::RecurrenceRule;
+use crate::commands::reminder::reminder_lead_minutes;
+use crate::commands::session::{map_focus_session, parse_session_timestamp, SESSION_COLUMNS};
+use crate::commands::task::{
+    apply_task_update, insert_task, is_task_occurrence, load_user_task_occurrences, load_user_tasks,
+    map_task, move_occurrence, parse_task_date, set_task_completed, skip_occurrence, TASK_SELECT,
+};
+use crate::db::Database;
+use crate::models::{
+    CreateTaskRequest, FocusSession, Task, TaskCategory, TaskOccurrence, TaskPriority,
+    UpdateTaskRequest,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use tauri::State;
+
+/// Exported tasks that did not come from a calendar get the UID
+/// `<task id>@flow-app`, which maps back to the task on re-import
+const EXPORT_UID_DOMAIN: &str = "flow-app";
+
+/// Non-standard property carrying a custom task category
+const CATEGORY_PROPERTY: &str = "X-FLOW-CATEGORY";
+
+const PRODUCT_ID: &str = "-//FLOW//Learning Time Tracker//EN";
+
+/// ICS import result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IcsImportResult {
+    pub tasks_imported: i64,
+    pub tasks_updated: i64,
+    pub tasks_unchanged: i64,
+    pub errors: Vec<String>,   // entries that could not be imported, with the reason
+}
+
+/// Import tasks from an iCalendar (.ics) file. VEVENTs become Event tasks,
+/// VTODOs To Do tasks, and entries with a VALARM Reminder tasks. Entries are
+/// matched by UID, so importing the same file again updates the tasks it
+/// created instead of adding duplicates.
+#[tauri::command]
+pub fn import_ics(db: State<Database>, user_id: String, path: String) -> Result<IcsImportResult, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    import_calendar(&conn, &user_id, &content)
+}
+
+/// Export all of a user's tasks to an iCalendar (.ics) file, returning the
+/// number of tasks written. Times are written as local (floating) times.
+#[tauri::command]
+pub fn export_ics(db: State<Database>, user_id: String, path: String) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (content, count) = export_calendar(&conn, &user_id)?;
+    fs::write(&path, content).map_err(|e| format!("Could not write {}: {}", path, e))?;
+
+    Ok(count)
+}
+
+/// Export the focus sessions started between `start_date` and `end_date`
+/// (inclusive, user days) to an iCalendar (.ics) file as timed events, so
+/// study time can be laid over a calendar. Returns the number of sessions
+/// written.
+#[tauri::command]
+pub fn export_sessions_ics(
+    db: State<Database>,
+    user_id: String,
+    path: String,
+    start_date: String,
+    end_date: String,
+) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let start = parse_task_date(&start_date)?;
+    let end = parse_task_date(&end_date)?;
+    if end < start {
+        return Err("End date must not be before start date".to_string());
+    }
+
+    let (content, count) = export_session_calendar(&conn, &user_id, &format_date(start), &format_date(end))?;
+    fs::write(&path, content).map_err(|e| format!("Could not write {}: {}", path, e))?;
+
+    Ok(count)
+}
+
+/// A VEVENT or VTODO read as task fields, with times in the user's zone
+struct CalendarEntry {
+    uid: String,
+    title: String,
+    category: TaskCategory,
+    date: String,
+    start_time: String,
+    end_time: String,
+    recurrence: Option<String>,
+    priority: TaskPriority,
+    tags: Vec<String>,
+    /// Dates removed from a repeating entry (EXDATE)
+    excluded_dates: Vec<String>,
+    completed: bool,
+    completed_at: Option<String>,
+}
+
+enum ImportOutcome {
+    Imported,
+    Updated,
+    Unchanged,
+    /// Cancelled entries are not imported
+    Ignored,
+}
+
+fn import_calendar(conn: &rusqlite::Connection, user_id: &str, content: &str) -> Result<IcsImportResult, String> {
+    let calendars: Vec<Component> = parse_calendar(content)?
+        .into_iter()
+        .filter(|component| component.name == "VCALENDAR")
+        .collect();
+    if calendars.is_empty() {
+        return Err("The file does not contain a calendar".to_string());
+    }
+
+    let clock = UserClock::load(conn, user_id)?;
+    let mut result = IcsImportResult {
+        tasks_imported: 0,
+        tasks_updated: 0,
+        tasks_unchanged: 0,
+        errors: Vec::new(),
+    };
+
+    let entries = calendars
+        .iter()
+        .flat_map(|calendar| calendar.components.iter())
+        .filter(|component| component.name == "VEVENT" || component.name == "VTODO");
+
+    // Series go first so that changed occurrences (RECURRENCE-ID) find them
+    let (occurrence_changes, series): (Vec<&Component>, Vec<&Component>) =
+        entries.partition(|component| component.property("RECURRENCE-ID").is_some());
+
+    for component in series {
+        match import_entry(conn, user_id, &clock, component) {
+            Ok(ImportOutcome::Imported) => result.tasks_imported += 1,
+            Ok(ImportOutcome::Updated) => result.tasks_updated += 1,
+            Ok(ImportOutcome::Unchanged) => result.tasks_unchanged += 1,
+            Ok(ImportOutcome::Ignored) => {}
+            Err(e) => result.errors.push(format!("{}: {}", describe(component), e)),
+        }
+    }
+
+    for component in occurrence_changes {
+        if let Err(e) = import_occurrence_change(conn, user_id, &clock, component) {
+            result.errors.push(format!("{}: {}", describe(component), e));
+        }
+    }
+
+    Ok(result)
+}
+
+fn import_entry(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    clock: &UserClock,
+    component: &Component,
+) -> Result<ImportOutcome, String> {
+    let Some(entry) = read_entry(clock, component)? else {
+        return Ok(ImportOutcome::Ignored);
+    };
+
+    let (task, outcome) = match find_task_by_uid(conn, user_id, &entry.uid)? {
+        Some(task) => match entry_changes(&task, &entry) {
+            Some(request) => (apply_task_update(conn, &task.id, &request)?, ImportOutcome::Updated),
+            None => (task, ImportOutcome::Unchanged),
+        },
+        None => {
+            let request = CreateTaskRequest {
+                user_id: user_id.to_string(),
+                title: entry.title.clone(),
+                category: entry.category.clone(),
+                date: entry.date.clone(),
+                start_time: entry.start_time.clone(),
+                end_time: entry.end_time.clone(),
+                estimated_minutes: None,
+                recurrence: entry.recurrence.clone(),
+                auto_complete: None,
+                priority: Some(entry.priority),
+                tags: Some(entry.tags.clone()),
+            };
+            let task = insert_task(conn, &request)?;
+            conn.execute(
+                "UPDATE tasks SET ical_uid = ?1 WHERE id = ?2",
+                params![entry.uid, task.id],
+            )
+            .map_err(|e| e.to_string())?;
+            (task, ImportOutcome::Imported)
+        }
+    };
+
+    if task.recurrence.is_some() {
+        // Excluded dates the rule never produces are ignored
+        for date in entry.excluded_dates.iter().filter(|date| is_task_occurrence(&task, date)) {
+            skip_occurrence(conn, &task.id, date)?;
+        }
+    } else if entry.completed && !task.completed {
+        set_task_completed(conn, &task.id, None, true)?;
+        if let Some(ref completed_at) = entry.completed_at {
+            conn.execute(
+                "UPDATE tasks SET completed_at = ?1 WHERE id = ?2",
+                params![completed_at, task.id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Apply an entry that changes one occurrence of an imported repeating
+/// task: cancelled occurrences are skipped, others moved or completed
+fn import_occurrence_change(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    clock: &UserClock,
+    component: &Component,
+) -> Result<(), String> {
+    let uid = component
+        .text("UID")
+        .ok_or_else(|| "Missing UID".to_string())?;
+    let series = find_task_by_uid(conn, user_id, uid.trim())?
+        .filter(|task| task.recurrence.is_some())
+        .ok_or_else(|| "No repeating task with this UID".to_string())?;
+
+    let recurrence_id = component
+        .property("RECURRENCE-ID")
+        .ok_or_else(|| "Missing RECURRENCE-ID".to_string())?
+        .date_value()?;
+    let occurrence_date = format_date(local_date_time(clock, recurrence_id).0);
+
+    let Some(entry) = read_entry(clock, component)? else {
+        return skip_occurrence(conn, &series.id, &occurrence_date);
+    };
+
+    if entry.date != occurrence_date || entry.start_time != series.start_time || entry.end_time != series.end_time {
+        move_occurrence(
+            conn,
+            &series.id,
+            &occurrence_date,
+            &entry.date,
+            Some(&entry.start_time),
+            Some(&entry.end_time),
+        )?;
+    }
+    if entry.completed {
+        set_task_completed(conn, &series.id, Some(&occurrence_date), true)?;
+    }
+
+    Ok(())
+}
+
+/// Read a VEVENT or VTODO; `None` when it is cancelled
+fn read_entry(clock: &UserClock, component: &Component) -> Result<Option<CalendarEntry>, String> {
+    let status = component.text("STATUS").map(|status| status.trim().to_ascii_uppercase());
+    if status.as_deref() == Some("CANCELLED") {
+        return Ok(None);
+    }
+    let is_todo = component.name == "VTODO";
+
+    let uid = component
+        .text("UID")
+        .map(|uid| uid.trim().to_string())
+        .filter(|uid| !uid.is_empty())
+        .ok_or_else(|| "Missing UID".to_string())?;
+
+    let title = component
+        .text("SUMMARY")
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Untitled".to_string());
+
+    let category = match component.text(CATEGORY_PROPERTY).and_then(|name| TaskCategory::from_str(&name)) {
+        Some(category) => category,
+        None if component.has_component("VALARM") => TaskCategory::Reminder,
+        None if is_todo => TaskCategory::ToDo,
+        None => TaskCategory::Event,
+    };
+
+    // To-dos may only have a due date; their due time ends the task
+    let start = component.property("DTSTART").map(|p| p.date_value()).transpose()?;
+    let due = component.property("DUE").map(|p| p.date_value()).transpose()?;
+    let end = match (component.property("DTEND"), component.property("DURATION"), start) {
+        (Some(end), _, _) => Some(end.date_value()?),
+        (None, Some(duration), Some(start)) => Some(shift(start, parse_duration(&duration.value)?)?),
+        _ => due,
+    };
+    let begin = start.or(due).ok_or_else(|| "Missing start date".to_string())?;
+
+    let (date, start_time) = local_date_time(clock, begin);
+    let (start_time, end_time) = match start_time {
+        // All-day entries fill the day
+        None => ("00:00".to_string(), "23:59".to_string()),
+        Some(start_time) => {
+            let end_time = match end.map(|end| local_date_time(clock, end)) {
+                Some((end_date, Some(end_time))) if end_date == date && end_time >= start_time => end_time,
+                // Entries running past midnight end with the day they start on
+                Some((end_date, _)) if end_date > date => NaiveTime::from_hms_opt(23, 59, 0).unwrap_or(start_time),
+                _ => start_time,
+            };
+            (format_time(start_time), format_time(end_time))
+        }
+    };
+
+    let recurrence = component
+        .property("RRULE")
+        .map(|rule| {
+            RecurrenceRule::parse(&rule.value)
+                .map(|rule| rule.to_string())
+                .map_err(|e| format!("Unsupported repeat rule ({})", e))
+        })
+        .transpose()?;
+
+    let mut excluded_dates = Vec::new();
+    for property in component.properties("EXDATE") {
+        for value in property.date_values()? {
+            excluded_dates.push(format_date(local_date_time(clock, value).0));
+        }
+    }
+
+    let priority = match component.text("PRIORITY").and_then(|value| value.trim().parse::<u8>().ok()) {
+        Some(1..=2) => TaskPriority::Urgent,
+        Some(3..=4) => TaskPriority::High,
+        Some(6..=9) => TaskPriority::Low,
+        _ => TaskPriority::Normal,
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    for property in component.properties("CATEGORIES") {
+        for tag in split_text_list(&property.value) {
+            if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(&tag)) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    let completed_property = component.property("COMPLETED");
+    let completed = is_todo && (status.as_deref() == Some("COMPLETED") || completed_property.is_some());
+    let completed_at = match completed_property {
+        Some(property) if completed => Some(instant(clock, property.date_value()?).to_rfc3339()),
+        _ => None,
+    };
+
+    Ok(Some(CalendarEntry {
+        uid,
+        title,
+        category,
+        date: format_date(date),
+        start_time,
+        end_time,
+        recurrence,
+        priority,
+        tags,
+        excluded_dates,
+        completed,
+        completed_at,
+    }))
+}
+
+/// Fields of an imported task that the calendar entry changes, if any
+fn entry_changes(task: &Task, entry: &CalendarEntry) -> Option<UpdateTaskRequest> {
+    let differs = |current: &str, new: &str| (current != new).then(|| new.to_string());
+
+    let mut current_tags: Vec<String> = task.tags.iter().map(|tag| tag.to_lowercase()).collect();
+    let mut new_tags: Vec<String> = entry.tags.iter().map(|tag| tag.to_lowercase()).collect();
+    current_tags.sort();
+    new_tags.sort();
+
+    let request = UpdateTaskRequest {
+        title: differs(&task.title, &entry.title),
+        category: (task.category != entry.category).then(|| entry.category.clone()),
+        date: differs(&task.date, &entry.date),
+        start_time: differs(&task.start_time, &entry.start_time),
+        end_time: differs(&task.end_time, &entry.end_time),
+        completed: None,
+        estimated_minutes: None,
+        recurrence: (task.recurrence != entry.recurrence).then(|| entry.recurrence.clone().unwrap_or_default()),
+        auto_complete: None,
+        priority: (task.priority != entry.priority).then_some(entry.priority),
+        tags: (current_tags != new_tags).then(|| entry.tags.clone()),
+    };
+
+    let changed = request.title.is_some()
+        || request.category.is_some()
+        || request.date.is_some()
+        || request.start_time.is_some()
+        || request.end_time.is_some()
+        || request.recurrence.is_some()
+        || request.priority.is_some()
+        || request.tags.is_some();
+    changed.then_some(request)
+}
+
+/// The task imported with a UID, or exported under it from this app
+fn find_task_by_uid(conn: &rusqlite::Connection, user_id: &str, uid: &str) -> Result<Option<Task>, String> {
+    let own_id = uid.strip_suffix(&format!("@{}", EXPORT_UID_DOMAIN));
+
+    conn.query_row(
+        &format!(
+            "{} WHERE t.user_id = ?1 AND (t.ical_uid = ?2 OR (t.ical_uid IS NULL AND t.id = ?3))
+             ORDER BY t.ical_uid IS NULL ASC
+             LIMIT 1",
+            TASK_SELECT
+        ),
+        params![user_id, uid, own_id],
+        map_task,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn export_calendar(conn: &rusqlite::Connection, user_id: &str) -> Result<(String, i64), String> {
+    let mut tasks = load_user_tasks(conn, user_id)?;
+    tasks.sort_by(|a, b| (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time)));
+
+    let mut occurrences: HashMap<String, Vec<TaskOccurrence>> = HashMap::new();
+    for occurrence in load_user_task_occurrences(conn, user_id)? {
+        occurrences
+            .entry(occurrence.task_id.clone())
+            .or_default()
+            .push(occurrence);
+    }
+
+    let lead_minutes = reminder_lead_minutes(conn, user_id)?;
+    let stamp = format_utc(Utc::now());
+
+    let mut writer = CalendarWriter::default();
+    writer.begin("VCALENDAR");
+    writer.property("VERSION", "2.0");
+    writer.property("PRODID", PRODUCT_ID);
+    writer.property("CALSCALE", "GREGORIAN");
+
+    let mut count = 0;
+    for task in &tasks {
+        let task_occurrences = occurrences.get(&task.id).map(Vec::as_slice).unwrap_or(&[]);
+        if write_task(&mut writer, task, task_occurrences, lead_minutes, &stamp) {
+            count += 1;
+        }
+    }
+
+    writer.end("VCALENDAR");
+    Ok((writer.finish(), count))
+}
+
+fn export_session_calendar(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<(String, i64), String> {
+    let clock = UserClock::load(conn, user_id)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM focus_sessions WHERE user_id = ?1 ORDER BY started_at ASC",
+            SESSION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let sessions: Vec<FocusSession> = stmt
+        .query_map(params![user_id], map_focus_session)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let task_titles: HashMap<String, String> = load_user_tasks(conn, user_id)?
+        .into_iter()
+        .map(|task| (task.id, task.title))
+        .collect();
+
+    let stamp = format_utc(Utc::now());
+
+    let mut writer = CalendarWriter::default();
+    writer.begin("VCALENDAR");
+    writer.property("VERSION", "2.0");
+    writer.property("PRODID", PRODUCT_ID);
+    writer.property("CALSCALE", "GREGORIAN");
+
+    let mut count = 0;
+    for session in &sessions {
+        // Sessions with unreadable timestamps cannot be placed on a calendar
+        let (Ok(started_at), Ok(ended_at)) = (
+            parse_session_timestamp(&session.started_at),
+            parse_session_timestamp(&session.ended_at),
+        ) else {
+            continue;
+        };
+        let date = format_date(clock.date_of(started_at));
+        if date.as_str() < start_date || date.as_str() > end_date {
+            continue;
+        }
+
+        let task_title = session.task_id.as_ref().and_then(|task_id| task_titles.get(task_id));
+        write_session(&mut writer, session, started_at, ended_at, task_title, &stamp);
+        count += 1;
+    }
+
+    writer.end("VCALENDAR");
+    Ok((writer.finish(), count))
+}
+
+/// Write a session as a VEVENT in UTC with its category, task and notes in
+/// the description
+fn write_session(
+    writer: &mut CalendarWriter,
+    session: &FocusSession,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    task_title: Option<&String>,
+    stamp: &str,
+) {
+    let summary = match session.category.as_deref() {
+        Some(category) => format!("Focus: {}", category),
+        None => "Focus session".to_string(),
+    };
+
+    let mut description = vec![format!("Focused: {}", format_focus_time(session.duration_seconds))];
+    if let Some(ref category) = session.category {
+        description.push(format!("Category: {}", category));
+    }
+    if let Some(task_title) = task_title {
+        description.push(format!("Task: {}", task_title));
+    }
+    if let Some(notes) = session.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty()) {
+        description.push(format!("Notes: {}", notes));
+    }
+
+    writer.begin("VEVENT");
+    writer.text("UID", &format!("session-{}@{}", session.id, EXPORT_UID_DOMAIN));
+    writer.property("DTSTAMP", stamp);
+    writer.property("DTSTART", &format_utc(started_at));
+    writer.property("DTEND", &format_utc(ended_at.max(started_at)));
+    writer.text("SUMMARY", &summary);
+    writer.text("DESCRIPTION", &description.join("\n"));
+    if let Some(ref category) = session.category {
+        writer.text("CATEGORIES", category);
+    }
+    // Past study time should not block the calendar as busy
+    writer.property("TRANSP", "TRANSPARENT");
+    writer.end("VEVENT");
+}
+
+/// Write a task and the occurrences changed from its series. Tasks without
+/// a valid date and start time are left out.
+fn write_task(
+    writer: &mut CalendarWriter,
+    task: &Task,
+    occurrences: &[TaskOccurrence],
+    lead_minutes: u32,
+    stamp: &str,
+) -> bool {
+    let Some(start) = parse_local(&task.date, &task.start_time) else {
+        return false;
+    };
+    let kind = match task.category {
+        TaskCategory::Event | TaskCategory::Reminder => "VEVENT",
+        _ => "VTODO",
+    };
+    let uid = task
+        .ical_uid
+        .clone()
+        .unwrap_or_else(|| format!("{}@{}", task.id, EXPORT_UID_DOMAIN));
+
+    writer.begin(kind);
+    writer.text("UID", &uid);
+    writer.property("DTSTAMP", stamp);
+    writer.text("SUMMARY", &task.title);
+    write_times(writer, kind, start, parse_local(&task.date, &task.end_time));
+
+    if let Some(ref recurrence) = task.recurrence {
+        writer.property("RRULE", recurrence);
+        for occurrence in occurrences.iter().filter(|occurrence| occurrence.skipped) {
+            if let Some(excluded) = parse_local(&occurrence.occurrence_date, &task.start_time) {
+                writer.property("EXDATE", &format_local(excluded));
+            }
+        }
+    }
+    write_details(writer, task);
+    if kind == "VTODO" && task.recurrence.is_none() {
+        write_status(writer, task.completed, task.completed_at.as_deref());
+    }
+    if task.category == TaskCategory::Reminder {
+        write_alarm(writer, &task.title, lead_minutes);
+    }
+    writer.end(kind);
+
+    if task.recurrence.is_none() {
+        return true;
+    }
+
+    for occurrence in occurrences.iter().filter(|occurrence| !occurrence.skipped) {
+        let moved = occurrence.date.is_some() || occurrence.start_time.is_some() || occurrence.end_time.is_some();
+        let completed_todo = kind == "VTODO" && occurrence.completed;
+        if !moved && !completed_todo {
+            continue;
+        }
+        let Some(recurrence_id) = parse_local(&occurrence.occurrence_date, &task.start_time) else {
+            continue;
+        };
+        let date = occurrence.date.as_deref().unwrap_or(&occurrence.occurrence_date);
+        let Some(start) = parse_local(date, occurrence.start_time.as_deref().unwrap_or(&task.start_time)) else {
+            continue;
+        };
+        let end = parse_local(date, occurrence.end_time.as_deref().unwrap_or(&task.end_time));
+
+        writer.begin(kind);
+        writer.text("UID", &uid);
+        writer.property("DTSTAMP", stamp);
+        writer.property("RECURRENCE-ID", &format_local(recurrence_id));
+        writer.text("SUMMARY", &task.title);
+        write_times(writer, kind, start, end);
+        write_details(writer, task);
+        if kind == "VTODO" {
+            write_status(writer, occurrence.completed, occurrence.completed_at.as_deref());
+        }
+        if task.category == TaskCategory::Reminder {
+            write_alarm(writer, &task.title, lead_minutes);
+        }
+        writer.end(kind);
+    }
+
+    true
+}
+
+/// Start plus DTEND (events) or DUE (to-dos); an end before the start is
+/// written as the start
+fn write_times(writer: &mut CalendarWriter, kind: &str, start: NaiveDateTime, end: Option<NaiveDateTime>) {
+    let end = end.filter(|end| *end >= start).unwrap_or(start);
+    writer.property("DTSTART", &format_local(start));
+    writer.property(if kind == "VTODO" { "DUE" } else { "DTEND" }, &format_local(end));
+}
+
+/// Priority, tags and custom category
+fn write_details(writer: &mut CalendarWriter, task: &Task) {
+    let priority = match task.priority {
+        TaskPriority::Urgent => Some("1"),
+        TaskPriority::High => Some("3"),
+        TaskPriority::Normal => None,
+        TaskPriority::Low => Some("9"),
+    };
+    if let Some(priority) = priority {
+        writer.property("PRIORITY", priority);
+    }
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| escape_text(tag)).collect();
+        writer.property("CATEGORIES", &tags.join(","));
+    }
+    if let TaskCategory::Custom(ref name) = task.category {
+        writer.text(CATEGORY_PROPERTY, name);
+    }
+}
+
+fn write_status(writer: &mut CalendarWriter, completed: bool, completed_at: Option<&str>) {
+    if !completed {
+        writer.property("STATUS", "NEEDS-ACTION");
+        return;
+    }
+    writer.property("STATUS", "COMPLETED");
+    if let Some(completed_at) = completed_at.and_then(|value| DateTime::parse_from_rfc3339(value).ok()) {
+        writer.property("COMPLETED", &format_utc(completed_at.with_timezone(&Utc)));
+    }
+}
+
+/// Display alarm firing the user's reminder lead time before the start
+fn write_alarm(writer: &mut CalendarWriter, title: &str, lead_minutes: u32) {
+    writer.begin("VALARM");
+    writer.property("ACTION", "DISPLAY");
+    writer.text("DESCRIPTION", title);
+    writer.property("TRIGGER", &format!("-PT{}M", lead_minutes));
+    writer.end("VALARM");
+}
+
+/// Day and, unless all-day, wall-clock time of a value in the user's zone
+fn local_date_time(clock: &UserClock, value: DateValue) -> (NaiveDate, Option<NaiveTime>) {
+    let local = match value {
+        DateValue::Date(date) => return (date, None),
+        DateValue::Floating(local) => local,
+        other => clock.local_datetime(instant(clock, other)),
+    };
+    (local.date(), Some(local.time()))
+}
+
+/// Instant of a value; floating and all-day values are in the user's zone
+fn instant(clock: &UserClock, value: DateValue) -> DateTime<Utc> {
+    match value {
+        DateValue::Date(date) => clock.local_instant(date.and_time(NaiveTime::MIN)),
+        DateValue::Floating(local) => clock.local_instant(local),
+        DateValue::Utc(utc) => Utc.from_utc_datetime(&utc),
+        DateValue::Zoned(local, zone) => resolve_local(&zone, local),
+    }
+}
+
+/// A value moved by `duration`, failing when it leaves the supported range
+fn shift(value: DateValue, duration: chrono::Duration) -> Result<DateValue, String> {
+    let shifted = match value {
+        DateValue::Date(date) => date
+            .checked_add_signed(chrono::Duration::days(duration.num_days()))
+            .map(DateValue::Date),
+        DateValue::Floating(local) => local.checked_add_signed(duration).map(DateValue::Floating),
+        DateValue::Utc(utc) => utc.checked_add_signed(duration).map(DateValue::Utc),
+        DateValue::Zoned(local, zone) => local
+            .checked_add_signed(duration)
+            .map(|local| DateValue::Zoned(local, zone)),
+    };
+    shifted.ok_or_else(|| "Duration is out of range".to_string())
+}
+
+/// A task date and HH:MM time as a local date-time
+fn parse_local(date: &str, time: &str) -> Option<NaiveDateTime> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+        .ok()?;
+    Some(date.and_time(time))
+}
+
+/// Focused time such as "1h 25m" or "40m"
+fn format_focus_time(seconds: i64) -> String {
+    let minutes = (seconds.max(0) + 30) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn format_time(time: NaiveTime) -> String {
+    time.format("%H:%M").to_string()
+}
+
+fn format_local(local: NaiveDateTime) -> String {
+    local.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn format_utc(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Name of an entry for error messages
+fn describe(component: &Component) -> String {
+    match (component.text("SUMMARY"), component.text("UID")) {
+        (Some(summary), _) if !summary.trim().is_empty() => format!("\"{}\"", summary.trim()),
+        (_, Some(uid)) => format!("Entry {}", uid.trim()),
+        _ => format!("Unnamed {}", component.name),
+    }
+}
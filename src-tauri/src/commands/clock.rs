@@ -0,0 +1,161 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::BTreeMap;
+use chrono_tz::Tz;
+use rusqlite::{params, OptionalExtension};
+
+/// User setting holding an IANA time zone name (e.g. "Asia/Shanghai")
+pub(crate) const TIME_ZONE_KEY: &str = "time_zone";
+
+/// User setting holding the hour (0-23) at which a new day starts
+pub(crate) const DAY_START_HOUR_KEY: &str = "day_start_hour";
+
+/// Time zone used to turn instants into calendar days for a user
+#[derive(Debug, Clone, Copy)]
+enum UserTimeZone {
+    /// No zone configured: follow the machine's local zone
+    System,
+    Named(Tz),
+}
+
+/// A user's view of "today" and of which day a timestamp belongs to.
+/// All stats, streak and heatmap date math should go through this.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UserClock {
+    zone: UserTimeZone,
+    /// Local hour at which the day rolls over; time before it counts toward the previous day
+    day_start_hour: u32,
+}
+
+impl UserClock {
+    /// Load the clock for a user from their settings
+    pub(crate) fn load(conn: &rusqlite::Connection, user_id: &str) -> Result<Self, String> {
+        // Invalid stored values fall back to the defaults rather than failing stats
+        let zone = get_user_setting(conn, user_id, TIME_ZONE_KEY)?
+            .and_then(|name| parse_time_zone(&name).ok())
+            .map_or(UserTimeZone::System, UserTimeZone::Named);
+
+        let day_start_hour = get_user_setting(conn, user_id, DAY_START_HOUR_KEY)?
+            .and_then(|value| parse_day_start_hour(&value).ok())
+            .unwrap_or(0);
+
+        Ok(Self { zone, day_start_hour })
+    }
+
+    /// Wall-clock time in the user's zone
+    pub(crate) fn local_datetime(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self.zone {
+            UserTimeZone::System => instant.with_timezone(&chrono::Local).naive_local(),
+            UserTimeZone::Named(tz) => instant.with_timezone(&tz).naive_local(),
+        }
+    }
+
+    /// Calendar day an instant counts toward for the user
+    pub(crate) fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        let shifted = self.local_datetime(instant) - chrono::Duration::hours(self.day_start_hour as i64);
+        shifted.date()
+    }
+
+    pub(crate) fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+
+    /// Calendar day (YYYY-MM-DD) of a stored session timestamp
+    pub(crate) fn date_from_iso(&self, value: &str) -> Result<String, String> {
+        if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+            let date = self.date_of(parsed.with_timezone(&Utc));
+            return Ok(date.format("%Y-%m-%d").to_string());
+        }
+
+        // Legacy values without an offset are read as their leading date
+        value
+            .get(..10)
+            .filter(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok())
+            .map(|date| date.to_string())
+            .ok_or_else(|| format!("Invalid timestamp: {}", value))
+    }
+
+    /// Instant at which the given user day begins
+    pub(crate) fn day_start(&self, date: NaiveDate) -> DateTime<Utc> {
+        let local = date
+            .and_hms_opt(self.day_start_hour, 0, 0)
+            .unwrap_or_else(|| date.and_hms_opt(0, 0, 0).unwrap_or_default());
+
+        self.local_instant(local)
+    }
+
+    /// Instant of a wall-clock time in the user's zone
+    pub(crate) fn local_instant(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self.zone {
+            UserTimeZone::System => resolve_local(&chrono::Local, local),
+            UserTimeZone::Named(tz) => resolve_local(&tz, local),
+        }
+    }
+
+    /// Seconds of wall-clock time in `[start, end)` that fall on each user day
+    pub(crate) fn split_by_day(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BTreeMap<NaiveDate, i64> {
+        let mut seconds_by_day = BTreeMap::new();
+        let mut cursor = start;
+
+        while cursor < end {
+            let date = self.date_of(cursor);
+            let next_day = self.day_start(date + chrono::Duration::days(1));
+            // Guard against a boundary that does not move forward (e.g. odd DST data)
+            let piece_end = if next_day > cursor { next_day.min(end) } else { end };
+
+            *seconds_by_day.entry(date).or_insert(0) += (piece_end - cursor).num_seconds();
+            cursor = piece_end;
+        }
+
+        seconds_by_day
+    }
+}
+
+/// Resolve a local wall-clock time to an instant. Times skipped by a DST jump
+/// resolve to the first valid time after them.
+pub(crate) fn resolve_local<Z: TimeZone>(zone: &Z, local: NaiveDateTime) -> DateTime<Utc> {
+    let mut candidate = local;
+    for _ in 0..(24 * 4) {
+        if let Some(resolved) = zone.from_local_datetime(&candidate).earliest() {
+            return resolved.with_timezone(&Utc);
+        }
+        candidate += chrono::Duration::minutes(15);
+    }
+    Utc.from_utc_datetime(&local)
+}
+
+pub(crate) fn get_user_setting(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    key: &str,
+) -> Result<Option<String>, String> {
+    let value: Option<Option<String>> = conn
+        .query_row(
+            "SELECT value FROM user_settings WHERE user_id = ?1 AND key = ?2",
+            params![user_id, key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(value.flatten())
+}
+
+/// Validate a day start hour setting value
+pub(crate) fn parse_day_start_hour(value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|hour| *hour < 24)
+        .ok_or_else(|| format!("Day start hour must be between 0 and 23: {}", value))
+}
+
+/// Validate an IANA time zone name
+pub(crate) fn parse_time_zone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>()
+        .map_err(|_| format!("Unknown time zone: {}", name))
+}
@@ -0,0 +1,114 @@
+/// One parsed CSV record with the line it starts on (1-based), for error messages
+#[derive(Debug, Clone)]
+pub(crate) struct CsvRecord {
+    pub line: i64,
+    pub fields: Vec<String>,
+}
+
+/// Builds RFC 4180 CSV text with CRLF line endings
+#[derive(Debug, Default)]
+pub(crate) struct CsvWriter {
+    output: String,
+}
+
+impl CsvWriter {
+    pub(crate) fn record<I, S>(&mut self, fields: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let fields: Vec<String> = fields.into_iter().map(|field| quote_field(field.as_ref())).collect();
+        self.output.push_str(&fields.join(","));
+        self.output.push_str("\r\n");
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.output
+    }
+}
+
+/// Parse CSV text. The delimiter (comma, semicolon or tab) is taken from
+/// the first line, as spreadsheets in some locales save with semicolons.
+/// Quoted fields may contain delimiters, doubled quotes and line breaks;
+/// blank lines are skipped.
+pub(crate) fn parse_csv(content: &str) -> Result<Vec<CsvRecord>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let delimiter = detect_delimiter(content);
+
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut fields));
+                line += 1;
+                record_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Unclosed quote in the record starting on line {}", record_line));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        push_record(&mut records, record_line, fields);
+    }
+
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<CsvRecord>, line: i64, fields: Vec<String>) {
+    if fields.iter().all(|field| field.trim().is_empty()) {
+        return;
+    }
+    records.push(CsvRecord { line, fields });
+}
+
+fn detect_delimiter(content: &str) -> char {
+    let first_line = content.lines().next().unwrap_or("");
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|delimiter| first_line.matches(*delimiter).count())
+        .filter(|delimiter| first_line.contains(*delimiter))
+        .unwrap_or(',')
+}
+
+fn quote_field(field: &str) -> String {
+    let needs_quotes = field.contains([',', '"', '\r', '\n'])
+        || field.starts_with(' ')
+        || field.ends_with(' ');
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
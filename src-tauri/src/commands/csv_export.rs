@@ -0,0 +1,439 @@
+use crate::commands::clock::UserClock;
+use crate::commands::csv::{parse_csv, CsvRecord, CsvWriter};
+use crate::commands::session::{
+    find_overlapping_sessions_internal, insert_focus_session, map_focus_session,
+    parse_session_timestamp, validate_manual_session, SESSION_COLUMNS,
+};
+use crate::commands::task::{load_user_tasks, parse_task_date};
+use crate::db::Database;
+use crate::models::{CreateFocusSessionRequest, FocusSession};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use tauri::State;
+
+/// Columns of the session CSV, in order. Existing columns keep their name and
+/// position; new ones are only ever appended.
+const SESSION_CSV_COLUMNS: [&str; 9] = [
+    "id",
+    "date",
+    "started_at",
+    "ended_at",
+    "duration_seconds",
+    "category",
+    "task_id",
+    "task_title",
+    "notes",
+];
+
+/// Columns of the task CSV, in order (append-only like the session columns)
+const TASK_CSV_COLUMNS: [&str; 14] = [
+    "id",
+    "title",
+    "category",
+    "date",
+    "start_time",
+    "end_time",
+    "completed",
+    "completed_at",
+    "priority",
+    "tags",
+    "recurrence",
+    "estimated_minutes",
+    "focus_seconds",
+    "created_at",
+];
+
+/// Columns of the daily stats CSV, in order (append-only)
+const DAILY_STATS_CSV_COLUMNS: [&str; 3] = ["date", "total_focus_seconds", "session_count"];
+
+/// Longest duration, in seconds, an imported row may carry
+const MAX_IMPORTED_DURATION_SECONDS: f64 = 366.0 * 24.0 * 3600.0;
+
+/// Which CSV column holds each session field. Fields left out use the column
+/// of the same name in the session export, if the file has one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCsvMapping {
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub duration_seconds: Option<String>,
+    pub duration_minutes: Option<String>,
+    pub category: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// A CSV row that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvRowError {
+    pub line: i64,   // line of the file the row starts on
+    pub message: String,
+}
+
+/// CSV import result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportResult {
+    pub rows_imported: i64,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// Export focus sessions started between `start_date` and `end_date`
+/// (inclusive user days, both optional) to a CSV file. Returns the number
+/// of rows written.
+#[tauri::command]
+pub fn export_sessions_csv(
+    db: State<Database>,
+    user_id: String,
+    path: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (start_date, end_date) = parse_date_range(start_date.as_deref(), end_date.as_deref())?;
+    let clock = UserClock::load(&conn, &user_id)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM focus_sessions WHERE user_id = ?1 ORDER BY started_at ASC",
+            SESSION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let sessions: Vec<FocusSession> = stmt
+        .query_map(params![user_id], map_focus_session)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let task_titles: HashMap<String, String> = load_user_tasks(&conn, &user_id)?
+        .into_iter()
+        .map(|task| (task.id, task.title))
+        .collect();
+
+    let mut writer = CsvWriter::default();
+    writer.record(SESSION_CSV_COLUMNS);
+
+    let mut count = 0;
+    for session in sessions {
+        let date = clock.date_from_iso(&session.started_at)?;
+        if !in_range(&date, start_date.as_deref(), end_date.as_deref()) {
+            continue;
+        }
+
+        let task_title = session
+            .task_id
+            .as_ref()
+            .and_then(|task_id| task_titles.get(task_id))
+            .cloned()
+            .unwrap_or_default();
+        writer.record([
+            session.id,
+            date,
+            session.started_at,
+            session.ended_at,
+            session.duration_seconds.to_string(),
+            session.category.unwrap_or_default(),
+            session.task_id.unwrap_or_default(),
+            task_title,
+            session.notes.unwrap_or_default(),
+        ]);
+        count += 1;
+    }
+
+    fs::write(&path, writer.finish()).map_err(|e| format!("Could not write {}: {}", path, e))?;
+    Ok(count)
+}
+
+/// Export all of a user's tasks to a CSV file, recurring tasks once as their
+/// series. Tags are separated by semicolons. Returns the number of rows written.
+#[tauri::command]
+pub fn export_tasks_csv(db: State<Database>, user_id: String, path: String) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut tasks = load_user_tasks(&conn, &user_id)?;
+    tasks.sort_by(|a, b| (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time)));
+
+    let mut writer = CsvWriter::default();
+    writer.record(TASK_CSV_COLUMNS);
+
+    for task in &tasks {
+        writer.record([
+            task.id.clone(),
+            task.title.clone(),
+            task.category.as_str().to_string(),
+            task.date.clone(),
+            task.start_time.clone(),
+            task.end_time.clone(),
+            task.completed.to_string(),
+            task.completed_at.clone().unwrap_or_default(),
+            task.priority.as_str().to_string(),
+            task.tags.join(";"),
+            task.recurrence.clone().unwrap_or_default(),
+            task.estimated_minutes.map(|minutes| minutes.to_string()).unwrap_or_default(),
+            task.focus_seconds.to_string(),
+            task.created_at.clone(),
+        ]);
+    }
+
+    fs::write(&path, writer.finish()).map_err(|e| format!("Could not write {}: {}", path, e))?;
+    Ok(tasks.len() as i64)
+}
+
+/// Export daily focus totals between `start_date` and `end_date` (inclusive,
+/// both optional) to a CSV file. Returns the number of rows written.
+#[tauri::command]
+pub fn export_daily_stats_csv(
+    db: State<Database>,
+    user_id: String,
+    path: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let (start_date, end_date) = parse_date_range(start_date.as_deref(), end_date.as_deref())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT date, total_focus_seconds, session_count
+             FROM daily_stats
+             WHERE user_id = ?1 AND (?2 IS NULL OR date >= ?2) AND (?3 IS NULL OR date <= ?3)
+             ORDER BY date ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows: Vec<(String, i64, i64)> = stmt
+        .query_map(params![user_id, start_date, end_date], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut writer = CsvWriter::default();
+    writer.record(DAILY_STATS_CSV_COLUMNS);
+    for (date, total_focus_seconds, session_count) in &rows {
+        writer.record([date.clone(), total_focus_seconds.to_string(), session_count.to_string()]);
+    }
+
+    fs::write(&path, writer.finish()).map_err(|e| format!("Could not write {}: {}", path, e))?;
+    Ok(rows.len() as i64)
+}
+
+/// Get the header row of a CSV file, for choosing a column mapping
+#[tauri::command]
+pub fn get_csv_headers(path: String) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+
+    let records = parse_csv(&content)?;
+    let header = records.into_iter().next().ok_or_else(|| "The file is empty".to_string())?;
+    Ok(header.fields.into_iter().map(|field| field.trim().to_string()).collect())
+}
+
+/// Import focus sessions from a CSV file with a header row. Each row needs
+/// a start time plus an end time or a duration (seconds or minutes); times
+/// without an offset are in the user's time zone. Rows that are invalid or
+/// overlap an existing session (unless `allow_overlap` is set) are reported
+/// and skipped while the other rows are imported.
+#[tauri::command]
+pub fn import_sessions_csv(
+    db: State<Database>,
+    user_id: String,
+    path: String,
+    mapping: Option<SessionCsvMapping>,
+    allow_overlap: Option<bool>,
+) -> Result<CsvImportResult, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut records = parse_csv(&content)?.into_iter();
+    let header = records.next().ok_or_else(|| "The file is empty".to_string())?;
+    let columns = SessionColumns::resolve(&header, &mapping.unwrap_or_default())?;
+
+    let clock = UserClock::load(&conn, &user_id)?;
+    let allow_overlap = allow_overlap.unwrap_or(false);
+    let mut result = CsvImportResult {
+        rows_imported: 0,
+        errors: Vec::new(),
+    };
+
+    for record in records {
+        let imported = session_request(&clock, &user_id, &columns, &record).and_then(|request| {
+            validate_manual_session(&request)?;
+            if !allow_overlap {
+                let overlapping = find_overlapping_sessions_internal(
+                    &conn,
+                    &user_id,
+                    &request.started_at,
+                    &request.ended_at,
+                    None,
+                )?;
+                if !overlapping.is_empty() {
+                    return Err("Overlaps an existing session".to_string());
+                }
+            }
+            insert_focus_session(&conn, request)
+        });
+
+        match imported {
+            Ok(_) => result.rows_imported += 1,
+            Err(message) => result.errors.push(CsvRowError {
+                line: record.line,
+                message,
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Positions of the mapped session fields in a CSV row
+struct SessionColumns {
+    started_at: usize,
+    ended_at: Option<usize>,
+    duration_seconds: Option<usize>,
+    duration_minutes: Option<usize>,
+    category: Option<usize>,
+    notes: Option<usize>,
+}
+
+impl SessionColumns {
+    fn resolve(header: &CsvRecord, mapping: &SessionCsvMapping) -> Result<Self, String> {
+        let find = |mapped: &Option<String>, default: &str| -> Result<Option<usize>, String> {
+            let position = |name: &str| {
+                header
+                    .fields
+                    .iter()
+                    .position(|field| field.trim().eq_ignore_ascii_case(name.trim()))
+            };
+            match mapped {
+                Some(name) => position(name)
+                    .map(Some)
+                    .ok_or_else(|| format!("Column \"{}\" not found", name)),
+                None => Ok(position(default)),
+            }
+        };
+
+        let columns = Self {
+            started_at: find(&mapping.started_at, "started_at")?
+                .ok_or_else(|| "A column with the start time is required".to_string())?,
+            ended_at: find(&mapping.ended_at, "ended_at")?,
+            duration_seconds: find(&mapping.duration_seconds, "duration_seconds")?,
+            duration_minutes: find(&mapping.duration_minutes, "duration_minutes")?,
+            category: find(&mapping.category, "category")?,
+            notes: find(&mapping.notes, "notes")?,
+        };
+
+        if columns.ended_at.is_none() && columns.duration_seconds.is_none() && columns.duration_minutes.is_none() {
+            return Err("A column with the end time or the duration is required".to_string());
+        }
+        Ok(columns)
+    }
+}
+
+/// Turn a CSV row into a session request, filling in the end time or the
+/// duration from the other
+fn session_request(
+    clock: &UserClock,
+    user_id: &str,
+    columns: &SessionColumns,
+    record: &CsvRecord,
+) -> Result<CreateFocusSessionRequest, String> {
+    let field = |column: Option<usize>| {
+        column
+            .and_then(|column| record.fields.get(column))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    let started_at = field(Some(columns.started_at))
+        .ok_or_else(|| "Missing start time".to_string())
+        .and_then(|value| parse_csv_timestamp(clock, value))?;
+    let ended_at = field(columns.ended_at)
+        .map(|value| parse_csv_timestamp(clock, value))
+        .transpose()?;
+
+    let duration_seconds = match (field(columns.duration_seconds), field(columns.duration_minutes)) {
+        (Some(seconds), _) => Some(bounded_duration(parse_number(seconds, "duration")?)?),
+        (None, Some(minutes)) => Some(bounded_duration(parse_number(minutes, "duration")? * 60.0)?),
+        (None, None) => None,
+    };
+
+    let (ended_at, duration_seconds) = match (ended_at, duration_seconds) {
+        (Some(ended_at), Some(duration_seconds)) => (ended_at, duration_seconds),
+        (Some(ended_at), None) => (ended_at, (ended_at - started_at).num_seconds()),
+        (None, Some(duration_seconds)) => {
+            let ended_at = chrono::Duration::try_seconds(duration_seconds)
+                .and_then(|duration| started_at.checked_add_signed(duration))
+                .ok_or_else(|| "Duration is out of range".to_string())?;
+            (ended_at, duration_seconds)
+        }
+        (None, None) => return Err("Missing end time or duration".to_string()),
+    };
+
+    Ok(CreateFocusSessionRequest {
+        user_id: user_id.to_string(),
+        duration_seconds,
+        started_at: started_at.to_rfc3339(),
+        ended_at: ended_at.to_rfc3339(),
+        category: field(columns.category).map(str::to_string),
+        notes: field(columns.notes).map(str::to_string),
+        segments: None,
+        subject_id: None,
+        task_id: None,
+    })
+}
+
+/// RFC 3339 timestamps, or local date-times such as "2026-10-12 08:15" in
+/// the user's zone
+fn parse_csv_timestamp(clock: &UserClock, value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(instant) = parse_session_timestamp(value) {
+        return Ok(instant);
+    }
+
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y/%m/%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|local| clock.local_instant(local))
+        .ok_or_else(|| format!("Invalid time: {}", value))
+}
+
+fn parse_number(value: &str, name: &str) -> Result<f64, String> {
+    value
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("Invalid {}: {}", name, value))
+}
+
+/// Whole seconds of a parsed duration, rejecting values no session could last
+fn bounded_duration(seconds: f64) -> Result<i64, String> {
+    if seconds.abs() > MAX_IMPORTED_DURATION_SECONDS {
+        return Err("Duration is too long".to_string());
+    }
+    Ok(seconds.round() as i64)
+}
+
+/// Check optional inclusive range bounds and write them as YYYY-MM-DD, the
+/// form the dates they are compared with take
+fn parse_date_range(
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<(Option<String>, Option<String>), String> {
+    let start = start_date.map(parse_task_date).transpose()?;
+    let end = end_date.map(parse_task_date).transpose()?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err("End date must not be before start date".to_string());
+        }
+    }
+
+    let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+    Ok((start.map(format), end.map(format)))
+}
+
+fn in_range(date: &str, start_date: Option<&str>, end_date: Option<&str>) -> bool {
+    start_date.map_or(true, |start| date >= start) && end_date.map_or(true, |end| date <= end)
+}
@@ -1,21 +1,50 @@
+use crate::commands::session::{
+    attach_session_segments, insert_session_segments, map_focus_session, parse_session_timestamp,
+    rebuild_daily_stats_internal, validate_session_task, SESSION_COLUMNS,
+};
+use crate::commands::task::{load_user_task_occurrences, map_task, TASK_SELECT};
+use crate::commands::task_item::load_user_task_items;
+use crate::commands::task_history::load_user_task_events;
+use crate::commands::rollover::load_user_task_date_changes;
+use crate::commands::export_schema::{read_export, CURRENT_EXPORT_VERSION};
+use crate::commands::goal::{load_daily_goals, load_period_goals};
+use crate::commands::streak::load_streak_freezes;
+use crate::commands::subject::{find_subject_by_name, load_subjects, resolve_session_subject};
+use crate::commands::tag::{load_tags, set_task_tags};
 use crate::db::Database;
-use crate::models::{Achievement, AchievementType, FocusSession, Task, TaskCategory, User};
+use crate::models::{
+    Achievement, AchievementType, DailyGoal, FocusSession, PeriodGoal, StreakFreeze, Subject, Tag,
+    Task, TaskDateChange, TaskEvent, TaskItem, TaskOccurrence, User,
+};
+use chrono::DateTime;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::State;
 
-/// Export data structure
+/// Export data structure, in the current schema version. Older exports are
+/// upgraded to this shape by `export_schema::read_export` before import.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportData {
     pub version: String,
+    /// Version of FLOW that wrote the export
+    pub app_version: Option<String>,
     pub exported_at: String,
     pub user: User,
     pub focus_sessions: Vec<FocusSession>,
     pub tasks: Vec<Task>,
     pub achievements: Vec<Achievement>,
     pub settings: HashMap<String, String>,
+    pub streak_freezes: Vec<StreakFreeze>,
+    pub daily_goals: Vec<DailyGoal>,
+    pub period_goals: Vec<PeriodGoal>,
+    pub subjects: Vec<Subject>,
+    pub task_occurrences: Vec<TaskOccurrence>,
+    pub task_items: Vec<TaskItem>,
+    pub tags: Vec<Tag>,
+    pub task_date_changes: Vec<TaskDateChange>,
+    pub task_events: Vec<TaskEvent>,
 }
 
 /// Import result
@@ -27,6 +56,14 @@ pub struct ImportResult {
     pub tasks_imported: i64,
     pub achievements_imported: i64,
     pub settings_imported: i64,
+    pub streak_freezes_imported: i64,
+    pub goals_imported: i64,
+    pub subjects_imported: i64,
+    pub tags_imported: i64,
+    pub task_occurrences_imported: i64,
+    pub task_items_imported: i64,
+    pub task_date_changes_imported: i64,
+    pub task_events_imported: i64,
     pub message: String,
 }
 
@@ -59,52 +96,30 @@ pub fn export_all_data(db: State<Database>, user_id: String) -> Result<ExportDat
 
     // Get focus sessions
     let mut stmt = conn
-        .prepare(
-            "SELECT id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at 
-             FROM focus_sessions WHERE user_id = ?1 ORDER BY started_at",
-        )
+        .prepare(&format!(
+            "SELECT {} FROM focus_sessions WHERE user_id = ?1 ORDER BY started_at",
+            SESSION_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
-    let focus_sessions: Vec<FocusSession> = stmt
-        .query_map(params![user_id], |row| {
-            Ok(FocusSession {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                duration_seconds: row.get(2)?,
-                started_at: row.get(3)?,
-                ended_at: row.get(4)?,
-                category: row.get(5)?,
-                notes: row.get(6)?,
-                created_at: row.get(7)?,
-            })
-        })
+    let mut focus_sessions: Vec<FocusSession> = stmt
+        .query_map(params![user_id], map_focus_session)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
 
+    attach_session_segments(&conn, &mut focus_sessions)?;
+
     // Get tasks
     let mut stmt = conn
-        .prepare(
-            "SELECT id, user_id, title, category, date, start_time, end_time, completed, created_at 
-             FROM tasks WHERE user_id = ?1 ORDER BY date, start_time",
-        )
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 ORDER BY t.date, t.start_time",
+            TASK_SELECT
+        ))
         .map_err(|e| e.to_string())?;
 
     let tasks: Vec<Task> = stmt
-        .query_map(params![user_id], |row| {
-            let category_str: String = row.get(3)?;
-            Ok(Task {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                title: row.get(2)?,
-                category: TaskCategory::from_str(&category_str).unwrap_or(TaskCategory::ToDo),
-                date: row.get(4)?,
-                start_time: row.get(5)?,
-                end_time: row.get(6)?,
-                completed: row.get::<_, i32>(7)? == 1,
-                created_at: row.get(8)?,
-            })
-        })
+        .query_map(params![user_id], map_task)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -146,26 +161,56 @@ pub fn export_all_data(db: State<Database>, user_id: String) -> Result<ExportDat
         .filter_map(|r| r.ok())
         .collect();
 
+    let streak_freezes = load_streak_freezes(&conn, &user_id)?;
+    let daily_goals = load_daily_goals(&conn, &user_id)?;
+    let period_goals = load_period_goals(&conn, &user_id)?;
+    let subjects = load_subjects(&conn, &user_id)?;
+    let task_occurrences = load_user_task_occurrences(&conn, &user_id)?;
+    let task_items = load_user_task_items(&conn, &user_id)?;
+    let tags = load_tags(&conn, &user_id)?;
+    let task_date_changes = load_user_task_date_changes(&conn, &user_id)?;
+    let task_events = load_user_task_events(&conn, &user_id)?;
+
     Ok(ExportData {
-        version: "1.0".to_string(),
+        version: CURRENT_EXPORT_VERSION.to_string(),
+        app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
         exported_at: now,
         user,
         focus_sessions,
         tasks,
         achievements,
         settings,
+        streak_freezes,
+        daily_goals,
+        period_goals,
+        subjects,
+        task_occurrences,
+        task_items,
+        tags,
+        task_date_changes,
+        task_events,
     })
 }
 
-/// Import data from JSON
+/// Import data from JSON. Exports of older schema versions are upgraded
+/// first; exports from a newer version of FLOW are rejected.
 #[tauri::command]
-pub fn import_data(db: State<Database>, data: ExportData) -> Result<ImportResult, String> {
+pub fn import_data(db: State<Database>, data: serde_json::Value) -> Result<ImportResult, String> {
+    let (data, source_version) = read_export(data)?;
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let mut sessions_imported = 0i64;
     let mut tasks_imported = 0i64;
     let mut achievements_imported = 0i64;
     let mut settings_imported = 0i64;
+    let mut streak_freezes_imported = 0i64;
+    let mut goals_imported = 0i64;
+    let mut subjects_imported = 0i64;
+    let mut tags_imported = 0i64;
+    let mut task_occurrences_imported = 0i64;
+    let mut task_items_imported = 0i64;
+    let mut task_date_changes_imported = 0i64;
+    let mut task_events_imported = 0i64;
 
     let user_id = &data.user.id;
 
@@ -197,32 +242,47 @@ pub fn import_data(db: State<Database>, data: ExportData) -> Result<ImportResult
         .map_err(|e| e.to_string())?;
     }
 
-    // Import focus sessions
-    for session in &data.focus_sessions {
+    // Import subjects, mapping each to the local subject with the same name
+    let mut subject_ids: HashMap<String, String> = HashMap::new();
+    for subject in &data.subjects {
         let result = conn.execute(
-            "INSERT OR IGNORE INTO focus_sessions (id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT OR IGNORE INTO subjects (id, user_id, name, color, icon, archived, created_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
-                session.id,
+                subject.id,
                 user_id,
-                session.duration_seconds,
-                session.started_at,
-                session.ended_at,
-                session.category,
-                session.notes,
-                session.created_at
+                subject.name,
+                subject.color,
+                subject.icon,
+                if subject.archived { 1 } else { 0 },
+                subject.created_at
             ],
         );
-        if result.is_ok() {
-            sessions_imported += 1;
+        if let Ok(inserted) = result {
+            subjects_imported += inserted as i64;
+        }
+        if let Some(local) = find_subject_by_name(&conn, user_id, &subject.name)? {
+            subject_ids.insert(subject.id.clone(), local.id);
+        }
+    }
+
+    // Import tags; tags with a name already in use are merged into the local one
+    for tag in &data.tags {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO tags (id, user_id, name, color, created_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![tag.id, user_id, tag.name, tag.color, tag.created_at],
+        );
+        if let Ok(inserted) = result {
+            tags_imported += inserted as i64;
         }
     }
 
     // Import tasks
     for task in &data.tasks {
         let result = conn.execute(
-            "INSERT OR IGNORE INTO tasks (id, user_id, title, category, date, start_time, end_time, completed, created_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT OR IGNORE INTO tasks (id, user_id, title, category, date, start_time, end_time, completed, created_at, estimated_minutes, recurrence, auto_complete, priority, completed_at, ical_uid) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 task.id,
                 user_id,
@@ -232,14 +292,161 @@ pub fn import_data(db: State<Database>, data: ExportData) -> Result<ImportResult
                 task.start_time,
                 task.end_time,
                 if task.completed { 1 } else { 0 },
-                task.created_at
+                task.created_at,
+                task.estimated_minutes,
+                task.recurrence,
+                if task.auto_complete && task.recurrence.is_none() { 1 } else { 0 },
+                task.priority.as_str(),
+                task.completed_at.as_ref().filter(|_| task.completed).filter(|value| is_completion_time(value)),
+                task.ical_uid
             ],
         );
+        if let Ok(inserted) = result {
+            // Bad tags must not abort the import; the task stays, untagged
+            if inserted > 0 && !task.tags.is_empty() {
+                if let Err(e) = set_task_tags(&conn, user_id, &task.id, &task.tags) {
+                    log::warn!("Failed to tag imported task {}: {}", task.id, e);
+                }
+            }
+        }
         if result.is_ok() {
             tasks_imported += 1;
         }
     }
 
+    for occurrence in &data.task_occurrences {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO task_occurrences (id, task_id, occurrence_date, completed, skipped, date, start_time, end_time, completed_at) 
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?10
+             WHERE EXISTS(SELECT 1 FROM tasks WHERE id = ?2 AND user_id = ?9)",
+            params![
+                occurrence.id,
+                occurrence.task_id,
+                occurrence.occurrence_date,
+                if occurrence.completed { 1 } else { 0 },
+                if occurrence.skipped { 1 } else { 0 },
+                occurrence.date,
+                occurrence.start_time,
+                occurrence.end_time,
+                user_id,
+                occurrence.completed_at.as_ref().filter(|_| occurrence.completed).filter(|value| is_completion_time(value))
+            ],
+        );
+        if let Ok(inserted) = result {
+            task_occurrences_imported += inserted as i64;
+        }
+    }
+
+    for item in &data.task_items {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO task_items (id, task_id, title, completed, position, created_at) 
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6
+             WHERE EXISTS(SELECT 1 FROM tasks WHERE id = ?2 AND user_id = ?7)",
+            params![
+                item.id,
+                item.task_id,
+                item.title,
+                if item.completed { 1 } else { 0 },
+                item.position,
+                item.created_at,
+                user_id
+            ],
+        );
+        if let Ok(inserted) = result {
+            task_items_imported += inserted as i64;
+        }
+    }
+
+    for change in &data.task_date_changes {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO task_date_changes (id, task_id, from_date, to_date, reason, changed_at) 
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6
+             WHERE EXISTS(SELECT 1 FROM tasks WHERE id = ?2 AND user_id = ?7)",
+            params![
+                change.id,
+                change.task_id,
+                change.from_date,
+                change.to_date,
+                change.reason.as_str(),
+                change.changed_at,
+                user_id
+            ],
+        );
+        if let Ok(inserted) = result {
+            task_date_changes_imported += inserted as i64;
+        }
+    }
+
+    // Events may belong to tasks deleted since, so they are not tied to a task
+    for event in &data.task_events {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO task_events (id, task_id, user_id, occurrence_date, event_type, title, occurred_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                event.id,
+                event.task_id,
+                user_id,
+                event.occurrence_date,
+                event.event_type.as_str(),
+                event.title,
+                event.occurred_at
+            ],
+        );
+        if let Ok(inserted) = result {
+            task_events_imported += inserted as i64;
+        }
+    }
+
+    // Import focus sessions
+    for session in &data.focus_sessions {
+        // Sessions with unreadable times cannot be placed on a day
+        if parse_session_timestamp(&session.started_at).is_err()
+            || parse_session_timestamp(&session.ended_at).is_err()
+        {
+            continue;
+        }
+        // Older exports only carry the category text
+        let mapped_subject_id = session
+            .subject_id
+            .as_ref()
+            .and_then(|id| subject_ids.get(id));
+        let (subject_id, category) = resolve_session_subject(
+            &conn,
+            user_id,
+            mapped_subject_id.map(String::as_str),
+            session.category.as_deref(),
+        )?;
+        // Tasks are imported first; drop links to tasks that did not come along
+        let task_id = session
+            .task_id
+            .as_deref()
+            .filter(|task_id| validate_session_task(&conn, user_id, task_id).is_ok());
+
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO focus_sessions (id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at, subject_id, task_id) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                session.id,
+                user_id,
+                session.duration_seconds,
+                session.started_at,
+                session.ended_at,
+                category,
+                session.notes,
+                session.created_at,
+                subject_id,
+                task_id
+            ],
+        );
+        if let Ok(inserted) = result {
+            // Segments only belong to sessions that were not already present
+            if inserted > 0 {
+                insert_session_segments(&conn, &session.id, &session.segments)?;
+            }
+            sessions_imported += 1;
+        }
+    }
+
     // Import achievements
     for achievement in &data.achievements {
         let result = conn.execute(
@@ -271,15 +478,87 @@ pub fn import_data(db: State<Database>, data: ExportData) -> Result<ImportResult
         }
     }
 
+    // Import streak freezes
+    for freeze in &data.streak_freezes {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO streak_freezes (id, user_id, source, earned_for, granted_at, used_on) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                freeze.id,
+                user_id,
+                freeze.source,
+                freeze.earned_for,
+                freeze.granted_at,
+                freeze.used_on
+            ],
+        );
+        if let Ok(inserted) = result {
+            streak_freezes_imported += inserted as i64;
+        }
+    }
+
+    // Import daily goals (imported targets replace existing ones for the same weekday)
+    for goal in &data.daily_goals {
+        conn.execute(
+            "DELETE FROM daily_goals WHERE user_id = ?1 AND weekday IS ?2",
+            params![user_id, goal.weekday],
+        )
+        .map_err(|e| e.to_string())?;
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO daily_goals (id, user_id, weekday, target_minutes) 
+             VALUES (?1, ?2, ?3, ?4)",
+            params![goal.id, user_id, goal.weekday, goal.target_minutes],
+        );
+        if let Ok(inserted) = result {
+            goals_imported += inserted as i64;
+        }
+    }
+
+    for goal in &data.period_goals {
+        let result = conn.execute(
+            "INSERT OR IGNORE INTO period_goals (id, user_id, period, target_seconds, category) 
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![goal.id, user_id, goal.period.as_str(), goal.target_seconds, goal.category],
+        );
+        if let Ok(rows) = result {
+            goals_imported += rows as i64;
+        }
+    }
+
+    // Imported sessions bypass the stats cache, so derive it again
+    // (after settings, since the time zone decides which day a session falls on)
+    rebuild_daily_stats_internal(&conn, user_id, None, None)?;
+
+    let mut message = format!(
+        "Imported {} sessions, {} tasks, {} achievements, {} settings",
+        sessions_imported, tasks_imported, achievements_imported, settings_imported
+    );
+    if source_version < CURRENT_EXPORT_VERSION {
+        message.push_str(&format!(
+            " (upgraded from export format version {})",
+            source_version
+        ));
+    }
+
     Ok(ImportResult {
         success: true,
         sessions_imported,
         tasks_imported,
         achievements_imported,
         settings_imported,
-        message: format!(
-            "Imported {} sessions, {} tasks, {} achievements, {} settings",
-            sessions_imported, tasks_imported, achievements_imported, settings_imported
-        ),
+        streak_freezes_imported,
+        goals_imported,
+        subjects_imported,
+        tags_imported,
+        task_occurrences_imported,
+        task_items_imported,
+        task_date_changes_imported,
+        task_events_imported,
+        message,
     })
 }
+
+/// Completion times are stored as RFC 3339; anything else is dropped on import
+fn is_completion_time(value: &str) -> bool {
+    DateTime::parse_from_rfc3339(value).is_ok()
+}
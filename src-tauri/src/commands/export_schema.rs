@@ -0,0 +1,111 @@
+use crate::commands::data_export::ExportData;
+use serde_json::{Map, Value};
+
+/// Converts an export of the previous schema version to the next one
+type Upgrade = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// One version of the export file format
+struct ExportSchema {
+    version: u32,
+    /// Upgrade from the previous version; `None` for the first
+    upgrade: Option<Upgrade>,
+}
+
+/// Every export schema, oldest first. When `ExportData` changes shape, add a
+/// version here with an upgrade that brings files of the previous version
+/// to the new shape.
+const EXPORT_SCHEMAS: &[ExportSchema] = &[
+    // Written as "1.0". Sections added over time (subjects, goals, task
+    // occurrences, items, tags, date changes and events) may be missing.
+    ExportSchema {
+        version: 1,
+        upgrade: None,
+    },
+    // Every section is present and the app version is recorded
+    ExportSchema {
+        version: 2,
+        upgrade: Some(upgrade_to_v2),
+    },
+];
+
+/// Schema version written into new exports
+pub(crate) const CURRENT_EXPORT_VERSION: u32 = EXPORT_SCHEMAS[EXPORT_SCHEMAS.len() - 1].version;
+
+/// Read an export of any known schema version, upgrading it to the current
+/// shape. Returns the data and the version the file was written with.
+pub(crate) fn read_export(value: Value) -> Result<(ExportData, u32), String> {
+    let Value::Object(mut data) = value else {
+        return Err("Not a FLOW export file".to_string());
+    };
+
+    let version = export_version(&data)?;
+    if version > CURRENT_EXPORT_VERSION {
+        let made_by = match data.get("appVersion").and_then(Value::as_str) {
+            Some(app_version) => format!("FLOW {}", app_version),
+            None => "a newer version of FLOW".to_string(),
+        };
+        return Err(format!(
+            "This export was made by {} (format version {}); this version of FLOW reads up to format version {}. Update FLOW to import it.",
+            made_by, version, CURRENT_EXPORT_VERSION
+        ));
+    }
+    if !EXPORT_SCHEMAS.iter().any(|schema| schema.version == version) {
+        return Err(format!("Unknown export format version: {}", version));
+    }
+
+    for schema in EXPORT_SCHEMAS.iter().filter(|schema| schema.version > version) {
+        if let Some(upgrade) = schema.upgrade {
+            upgrade(&mut data).map_err(|e| format!("Could not upgrade the export to format version {}: {}", schema.version, e))?;
+        }
+        data.insert("version".to_string(), Value::String(schema.version.to_string()));
+    }
+
+    let export = serde_json::from_value(Value::Object(data)).map_err(|e| format!("Invalid export file: {}", e))?;
+    Ok((export, version))
+}
+
+/// Schema version of an export: "1.0" and "2" style strings or a number
+fn export_version(data: &Map<String, Value>) -> Result<u32, String> {
+    let version = match data.get("version") {
+        Some(Value::String(version)) => version.split('.').next().and_then(|major| major.trim().parse().ok()),
+        Some(Value::Number(version)) => version.as_u64().and_then(|version| u32::try_from(version).ok()),
+        _ => return Err("Not a FLOW export file: it has no format version".to_string()),
+    };
+
+    version.ok_or_else(|| format!("Invalid export format version: {}", data["version"]))
+}
+
+fn upgrade_to_v2(data: &mut Map<String, Value>) -> Result<(), String> {
+    if !data.get("user").is_some_and(Value::is_object) {
+        return Err("the export has no user".to_string());
+    }
+
+    for section in [
+        "focusSessions",
+        "tasks",
+        "achievements",
+        "streakFreezes",
+        "dailyGoals",
+        "periodGoals",
+        "subjects",
+        "taskOccurrences",
+        "taskItems",
+        "tags",
+        "taskDateChanges",
+        "taskEvents",
+    ] {
+        match data.get(section) {
+            None | Some(Value::Null) => {
+                data.insert(section.to_string(), Value::Array(Vec::new()));
+            }
+            Some(Value::Array(_)) => {}
+            Some(_) => return Err(format!("{} is not a list", section)),
+        }
+    }
+    if matches!(data.get("settings"), None | Some(Value::Null)) {
+        data.insert("settings".to_string(), Value::Object(Map::new()));
+    }
+    data.entry("appVersion").or_insert(Value::Null);
+
+    Ok(())
+}
@@ -0,0 +1,467 @@
+use crate::commands::clock::UserClock;
+use crate::commands::session::{
+    allocate_session_by_day, granularity_label, granularity_start, load_user_sessions,
+};
+use crate::commands::streak::{current_streak, frozen_dates, longest_streak};
+use crate::db::Database;
+use crate::models::{
+    CreatePeriodGoalRequest, DailyGoal, GoalPeriod, GoalProgress, PeriodGoal, PeriodGoalProgress,
+    StatsGranularity,
+};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::params;
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::State;
+use uuid::Uuid;
+
+/// Periods returned by progress history when the caller does not ask for a count
+const DEFAULT_PROGRESS_PERIODS: u32 = 12;
+
+/// Most periods a progress history may cover (ten years of weeks)
+const MAX_PROGRESS_PERIODS: u32 = 520;
+
+/// Most days a daily goal progress range may cover
+const MAX_PROGRESS_DAYS: i64 = 3660;
+
+/// Get the user's daily goals
+#[tauri::command]
+pub fn get_daily_goals(db: State<Database>, user_id: String) -> Result<Vec<DailyGoal>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    load_daily_goals(&conn, &user_id)
+}
+
+/// Set the daily goal for a weekday (0 = Sunday), or the default goal when no weekday is given
+#[tauri::command]
+pub fn set_daily_goal(
+    db: State<Database>,
+    user_id: String,
+    target_minutes: i64,
+    weekday: Option<i64>,
+) -> Result<DailyGoal, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    if target_minutes <= 0 {
+        return Err("Goal must be at least one minute".to_string());
+    }
+    validate_weekday(weekday)?;
+
+    delete_daily_goal_internal(&conn, &user_id, weekday)?;
+
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO daily_goals (id, user_id, weekday, target_minutes) VALUES (?1, ?2, ?3, ?4)",
+        params![id, user_id, weekday, target_minutes],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(DailyGoal {
+        id,
+        user_id,
+        weekday,
+        target_minutes,
+    })
+}
+
+/// Remove the daily goal for a weekday, or the default goal when no weekday is given
+#[tauri::command]
+pub fn delete_daily_goal(
+    db: State<Database>,
+    user_id: String,
+    weekday: Option<i64>,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    validate_weekday(weekday)?;
+    delete_daily_goal_internal(&conn, &user_id, weekday)
+}
+
+/// Get daily goal progress for a date range (defaults to today)
+#[tauri::command]
+pub fn get_goal_progress(
+    db: State<Database>,
+    user_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<GoalProgress>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let today = UserClock::load(&conn, &user_id)?.today();
+    let start = parse_optional_date(start_date.as_deref())?.unwrap_or(today);
+    let end = parse_optional_date(end_date.as_deref())?.unwrap_or(start.max(today));
+    if end < start {
+        return Err("End date must not be before start date".to_string());
+    }
+    if (end - start).num_days() >= MAX_PROGRESS_DAYS {
+        return Err(format!("A progress range can cover at most {} days", MAX_PROGRESS_DAYS));
+    }
+
+    let targets = DailyTargets::load(&conn, &user_id)?;
+    let focus = daily_focus_seconds(&conn, &user_id)?;
+
+    let progress = start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| {
+            let target_seconds = targets.target_seconds(date);
+            let focus_seconds = focus.get(&date).copied().unwrap_or(0);
+            GoalProgress {
+                date: date.format("%Y-%m-%d").to_string(),
+                target_seconds,
+                focus_seconds,
+                met: target_seconds.is_some_and(|target| focus_seconds >= target),
+            }
+        })
+        .collect();
+
+    Ok(progress)
+}
+
+/// Create a weekly or monthly focus goal
+#[tauri::command]
+pub fn create_period_goal(
+    db: State<Database>,
+    request: CreatePeriodGoalRequest,
+) -> Result<PeriodGoal, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    if request.target_seconds <= 0 {
+        return Err("Goal target must be positive".to_string());
+    }
+
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO period_goals (id, user_id, period, target_seconds, category) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            id,
+            request.user_id,
+            request.period.as_str(),
+            request.target_seconds,
+            request.category
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(PeriodGoal {
+        id,
+        user_id: request.user_id,
+        period: request.period,
+        target_seconds: request.target_seconds,
+        category: request.category,
+    })
+}
+
+/// Get the user's weekly and monthly goals
+#[tauri::command]
+pub fn get_period_goals(db: State<Database>, user_id: String) -> Result<Vec<PeriodGoal>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    load_period_goals(&conn, &user_id)
+}
+
+/// Delete a weekly or monthly goal
+#[tauri::command]
+pub fn delete_period_goal(db: State<Database>, goal_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM period_goals WHERE id = ?1", params![goal_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Get progress for the current period and the ones before it, oldest first
+#[tauri::command]
+pub fn get_period_goal_progress(
+    db: State<Database>,
+    goal_id: String,
+    periods: Option<u32>,
+) -> Result<Vec<PeriodGoalProgress>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let goal = conn
+        .query_row(
+            "SELECT id, user_id, period, target_seconds, category FROM period_goals WHERE id = ?1",
+            params![goal_id],
+            map_period_goal,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let count = periods.unwrap_or(DEFAULT_PROGRESS_PERIODS).max(1);
+    if count > MAX_PROGRESS_PERIODS {
+        return Err(format!("At most {} periods can be shown", MAX_PROGRESS_PERIODS));
+    }
+    let today = UserClock::load(&conn, &goal.user_id)?.today();
+
+    let mut ranges = Vec::new();
+    let mut start = period_start(goal.period, today);
+    for _ in 0..count {
+        let end = next_period_start(goal.period, start)?
+            .pred_opt()
+            .ok_or_else(|| "Date out of range".to_string())?;
+        ranges.push((start, end));
+        start = previous_period_start(goal.period, start)?;
+    }
+    ranges.reverse();
+
+    let focus = match &goal.category {
+        Some(category) => category_focus_seconds(&conn, &goal.user_id, category)?,
+        None => daily_focus_seconds(&conn, &goal.user_id)?,
+    };
+
+    let progress = ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let focus_seconds: i64 = focus.range(start..=end).map(|(_, seconds)| seconds).sum();
+            PeriodGoalProgress {
+                label: period_label(goal.period, start),
+                start_date: start.format("%Y-%m-%d").to_string(),
+                end_date: end.format("%Y-%m-%d").to_string(),
+                target_seconds: goal.target_seconds,
+                focus_seconds,
+                met: focus_seconds >= goal.target_seconds,
+            }
+        })
+        .collect();
+
+    Ok(progress)
+}
+
+/// Daily targets resolved per weekday
+pub(crate) struct DailyTargets {
+    default_seconds: Option<i64>,
+    weekday_seconds: [Option<i64>; 7],
+}
+
+impl DailyTargets {
+    pub(crate) fn load(conn: &rusqlite::Connection, user_id: &str) -> Result<Self, String> {
+        let mut targets = DailyTargets {
+            default_seconds: None,
+            weekday_seconds: [None; 7],
+        };
+
+        for goal in load_daily_goals(conn, user_id)? {
+            let seconds = Some(goal.target_minutes * 60);
+            match goal.weekday {
+                Some(weekday) if (0..7).contains(&weekday) => {
+                    targets.weekday_seconds[weekday as usize] = seconds
+                }
+                _ => targets.default_seconds = seconds,
+            }
+        }
+
+        Ok(targets)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.default_seconds.is_none() && self.weekday_seconds.iter().all(Option::is_none)
+    }
+
+    /// Target for a day; a weekday goal overrides the default
+    pub(crate) fn target_seconds(&self, date: NaiveDate) -> Option<i64> {
+        let weekday = date.weekday().num_days_from_sunday() as usize;
+        self.weekday_seconds[weekday].or(self.default_seconds)
+    }
+}
+
+/// Current and longest streaks of days the daily goal was met. Days without
+/// a goal and frozen days keep a streak alive without adding to it.
+pub(crate) fn goal_streaks(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    today: NaiveDate,
+) -> Result<(i64, i64), String> {
+    let targets = DailyTargets::load(conn, user_id)?;
+    if targets.is_empty() {
+        return Ok((0, 0));
+    }
+
+    let focus = daily_focus_seconds(conn, user_id)?;
+    let first_day = focus.keys().next().copied().unwrap_or(today).min(today);
+
+    let mut met = BTreeSet::new();
+    let mut bridged = frozen_dates(conn, user_id)?;
+    for date in first_day.iter_days().take_while(|date| *date <= today) {
+        match targets.target_seconds(date) {
+            Some(target) if focus.get(&date).copied().unwrap_or(0) >= target => {
+                met.insert(date);
+            }
+            Some(_) => {}
+            None => {
+                bridged.insert(date);
+            }
+        }
+    }
+
+    Ok((current_streak(&met, &bridged, today), longest_streak(&met, &bridged)))
+}
+
+pub(crate) fn load_daily_goals(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<DailyGoal>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, user_id, weekday, target_minutes
+             FROM daily_goals
+             WHERE user_id = ?1
+             ORDER BY IFNULL(weekday, -1) ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let goals = stmt
+        .query_map(params![user_id], |row| {
+            Ok(DailyGoal {
+                id: row.get(0)?,
+                user_id: row.get(1)?,
+                weekday: row.get(2)?,
+                target_minutes: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(goals)
+}
+
+pub(crate) fn load_period_goals(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<PeriodGoal>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, user_id, period, target_seconds, category
+             FROM period_goals
+             WHERE user_id = ?1
+             ORDER BY created_at ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let goals = stmt
+        .query_map(params![user_id], map_period_goal)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(goals)
+}
+
+fn map_period_goal(row: &rusqlite::Row) -> rusqlite::Result<PeriodGoal> {
+    let period_str: String = row.get(2)?;
+    Ok(PeriodGoal {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        period: GoalPeriod::from_str(&period_str).unwrap_or(GoalPeriod::Week),
+        target_seconds: row.get(3)?,
+        category: row.get(4)?,
+    })
+}
+
+fn delete_daily_goal_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    weekday: Option<i64>,
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM daily_goals WHERE user_id = ?1 AND weekday IS ?2",
+        params![user_id, weekday],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Focus seconds per day from the daily stats cache
+fn daily_focus_seconds(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<BTreeMap<NaiveDate, i64>, String> {
+    let mut stmt = conn
+        .prepare("SELECT date, total_focus_seconds FROM daily_stats WHERE user_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let rows: Vec<(String, i64)> = stmt
+        .query_map(params![user_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.into_iter()
+        .map(|(date, seconds)| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map(|date| (date, seconds))
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// Focus seconds per day from sessions in one category
+fn category_focus_seconds(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    category: &str,
+) -> Result<BTreeMap<NaiveDate, i64>, String> {
+    let clock = UserClock::load(conn, user_id)?;
+    let mut seconds_by_day = BTreeMap::new();
+
+    for session in load_user_sessions(conn, user_id)? {
+        let in_category = session
+            .category
+            .as_deref()
+            .is_some_and(|name| name.trim().eq_ignore_ascii_case(category.trim()));
+        if !in_category {
+            continue;
+        }
+        for (date, seconds) in allocate_session_by_day(&clock, &session)?.seconds_by_day {
+            let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+            *seconds_by_day.entry(date).or_insert(0) += seconds;
+        }
+    }
+
+    Ok(seconds_by_day)
+}
+
+fn period_granularity(period: GoalPeriod) -> StatsGranularity {
+    match period {
+        GoalPeriod::Week => StatsGranularity::Week,
+        GoalPeriod::Month => StatsGranularity::Month,
+    }
+}
+
+/// First day of the period containing `date`; weeks start on Monday (ISO 8601)
+fn period_start(period: GoalPeriod, date: NaiveDate) -> NaiveDate {
+    granularity_start(period_granularity(period), date)
+}
+
+fn next_period_start(period: GoalPeriod, start: NaiveDate) -> Result<NaiveDate, String> {
+    let date = match period {
+        GoalPeriod::Week => start.checked_add_signed(chrono::Duration::days(7)),
+        GoalPeriod::Month => start.checked_add_months(chrono::Months::new(1)),
+    };
+    date.ok_or_else(|| "Date out of range".to_string())
+}
+
+fn previous_period_start(period: GoalPeriod, start: NaiveDate) -> Result<NaiveDate, String> {
+    let date = match period {
+        GoalPeriod::Week => start.checked_sub_signed(chrono::Duration::days(7)),
+        GoalPeriod::Month => start.checked_sub_months(chrono::Months::new(1)),
+    };
+    date.ok_or_else(|| "Date out of range".to_string())
+}
+
+fn period_label(period: GoalPeriod, start: NaiveDate) -> String {
+    granularity_label(period_granularity(period), start)
+}
+
+fn validate_weekday(weekday: Option<i64>) -> Result<(), String> {
+    match weekday {
+        Some(day) if !(0..7).contains(&day) => {
+            Err("Weekday must be between 0 (Sunday) and 6 (Saturday)".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn parse_optional_date(value: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| e.to_string()))
+        .transpose()
+}
@@ -0,0 +1,359 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+
+/// Longest content line allowed before folding, in octets (RFC 5545 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A calendar component such as VCALENDAR, VEVENT, VTODO or VALARM
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Component {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+}
+
+/// One content line, e.g. `DTSTART;TZID=Europe/Berlin:20261012T081500`
+#[derive(Debug, Clone)]
+pub(crate) struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+/// Value of a DTSTART/DTEND/DUE/EXDATE/RECURRENCE-ID property
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DateValue {
+    /// All-day value (`VALUE=DATE`)
+    Date(NaiveDate),
+    /// Wall-clock time without a zone
+    Floating(NaiveDateTime),
+    /// UTC time (trailing `Z`)
+    Utc(NaiveDateTime),
+    /// Wall-clock time in a known IANA zone
+    Zoned(NaiveDateTime, Tz),
+}
+
+impl Component {
+    /// First property with the given name
+    pub(crate) fn property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    /// All properties with the given name
+    pub(crate) fn properties<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> + 'a {
+        self.properties
+            .iter()
+            .filter(move |property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Unescaped text of the first property with the given name
+    pub(crate) fn text(&self, name: &str) -> Option<String> {
+        self.property(name).map(|property| unescape_text(&property.value))
+    }
+
+    /// Whether a nested component with the given name exists
+    pub(crate) fn has_component(&self, name: &str) -> bool {
+        self.components
+            .iter()
+            .any(|component| component.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Property {
+    pub(crate) fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Date or date-time values of the property (EXDATE may list several)
+    pub(crate) fn date_values(&self) -> Result<Vec<DateValue>, String> {
+        let all_day = self.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE"));
+        let zone = self.param("TZID").and_then(|tzid| tzid.trim_start_matches('/').parse::<Tz>().ok());
+
+        self.value
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| parse_date_value(value, all_day, zone))
+            .collect()
+    }
+
+    /// The property's single date or date-time value
+    pub(crate) fn date_value(&self) -> Result<DateValue, String> {
+        self.date_values()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("{} has no value", self.name))
+    }
+}
+
+/// Parse iCalendar text into its top-level components (normally one
+/// VCALENDAR). Lines outside any component are ignored.
+pub(crate) fn parse_calendar(content: &str) -> Result<Vec<Component>, String> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for line in unfold_lines(content) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let property = parse_content_line(&line)?;
+
+        if property.name.eq_ignore_ascii_case("BEGIN") {
+            stack.push(Component {
+                name: property.value.trim().to_ascii_uppercase(),
+                ..Component::default()
+            });
+        } else if property.name.eq_ignore_ascii_case("END") {
+            let component = stack
+                .pop()
+                .ok_or_else(|| format!("Unexpected END:{}", property.value))?;
+            if !component.name.eq_ignore_ascii_case(property.value.trim()) {
+                return Err(format!(
+                    "Expected END:{} but found END:{}",
+                    component.name, property.value
+                ));
+            }
+            match stack.last_mut() {
+                Some(parent) => parent.components.push(component),
+                None => roots.push(component),
+            }
+        } else if let Some(component) = stack.last_mut() {
+            component.properties.push(property);
+        }
+    }
+
+    if let Some(component) = stack.last() {
+        return Err(format!("Missing END:{}", component.name));
+    }
+
+    Ok(roots)
+}
+
+/// Parse a duration such as `PT1H30M`, `P1D` or `-PT15M`
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid duration: {}", value);
+
+    let trimmed = value.trim();
+    let (negative, rest) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut seconds = 0i64;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let amount: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                let unit_seconds = match (unit, in_time) {
+                    ('W', false) => 7 * 24 * 3600,
+                    ('D', false) => 24 * 3600,
+                    ('H', true) => 3600,
+                    ('M', true) => 60,
+                    ('S', true) => 1,
+                    _ => return Err(invalid()),
+                };
+                seconds = amount
+                    .checked_mul(unit_seconds)
+                    .and_then(|part| seconds.checked_add(part))
+                    .ok_or_else(invalid)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+
+    Duration::try_seconds(if negative { -seconds } else { seconds }).ok_or_else(invalid)
+}
+
+/// Builds iCalendar text with CRLF line endings and folded long lines
+#[derive(Debug, Default)]
+pub(crate) struct CalendarWriter {
+    output: String,
+}
+
+impl CalendarWriter {
+    pub(crate) fn begin(&mut self, name: &str) {
+        self.line(&format!("BEGIN:{}", name));
+    }
+
+    pub(crate) fn end(&mut self, name: &str) {
+        self.line(&format!("END:{}", name));
+    }
+
+    /// Write a property whose value is already in iCalendar form
+    pub(crate) fn property(&mut self, name: &str, value: &str) {
+        self.line(&format!("{}:{}", name, value));
+    }
+
+    /// Write a TEXT property, escaping its value
+    pub(crate) fn text(&mut self, name: &str, value: &str) {
+        self.property(name, &escape_text(value));
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.output
+    }
+
+    fn line(&mut self, line: &str) {
+        let mut octets = 0;
+        for c in line.chars() {
+            if octets + c.len_utf8() > MAX_LINE_OCTETS {
+                self.output.push_str("\r\n ");
+                // The leading space of a continuation line counts toward its length
+                octets = 1;
+            }
+            self.output.push(c);
+            octets += c.len_utf8();
+        }
+        self.output.push_str("\r\n");
+    }
+}
+
+/// Escape a TEXT value (backslashes, separators and newlines)
+pub(crate) fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Undo TEXT escaping
+pub(crate) fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => text.push('\\'),
+        }
+    }
+    text
+}
+
+/// Split a TEXT list (e.g. CATEGORIES) on unescaped commas
+pub(crate) fn split_text_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' {
+            items.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    items.push(unescape_text(&current));
+
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Join lines continued with a leading space or tab, accepting LF or CRLF
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let (Some(continued), Some(last)) = (
+            raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')),
+            lines.last_mut(),
+        ) {
+            last.push_str(continued);
+        } else {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Split `NAME;PARAM=value;PARAM="quoted":value` into its parts
+fn parse_content_line(line: &str) -> Result<Property, String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut value = None;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => parts.push(std::mem::take(&mut current)),
+            ':' if !in_quotes => {
+                parts.push(std::mem::take(&mut current));
+                value = Some(line[index + 1..].to_string());
+                break;
+            }
+            c => current.push(c),
+        }
+    }
+    let value = value.ok_or_else(|| format!("Invalid calendar line: {}", line))?;
+
+    let mut parts = parts.into_iter();
+    let name = parts.next().unwrap_or_default().trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return Err(format!("Invalid calendar line: {}", line));
+    }
+    let params = parts
+        .filter_map(|part| {
+            part.split_once('=')
+                .map(|(key, value)| (key.trim().to_ascii_uppercase(), value.to_string()))
+        })
+        .collect();
+
+    Ok(Property { name, params, value })
+}
+
+fn parse_date_value(value: &str, all_day: bool, zone: Option<Tz>) -> Result<DateValue, String> {
+    let invalid = || format!("Invalid calendar date: {}", value);
+
+    if all_day || !value.contains('T') {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(DateValue::Date)
+            .map_err(|_| invalid());
+    }
+
+    let (local, utc) = match value.strip_suffix('Z') {
+        Some(local) => (local, true),
+        None => (value, false),
+    };
+    let datetime = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+
+    Ok(match (utc, zone) {
+        (true, _) => DateValue::Utc(datetime),
+        (false, Some(zone)) => DateValue::Zoned(datetime, zone),
+        (false, None) => DateValue::Floating(datetime),
+    })
+}
@@ -1,16 +1,43 @@
 pub mod user;
 pub mod session;
+pub mod timer;
 pub mod task;
+pub mod task_item;
+pub mod task_history;
 pub mod achievement;
+pub mod streak;
+pub mod goal;
+pub mod subject;
+pub mod tag;
+pub mod rollover;
+pub mod reminder;
 pub mod settings;
 pub mod data_export;
+pub mod calendar;
+pub mod csv_export;
 pub mod app;
+pub mod clock;
+pub mod recurrence;
+pub mod ical;
+pub mod csv;
+pub mod export_schema;
 
 // Re-export all commands for easy access
 pub use user::*;
 pub use session::*;
+pub use timer::*;
 pub use task::*;
+pub use task_item::*;
+pub use task_history::*;
 pub use achievement::*;
+pub use streak::*;
+pub use goal::*;
+pub use subject::*;
+pub use tag::*;
+pub use rollover::*;
+pub use reminder::*;
 pub use settings::*;
 pub use data_export::*;
+pub use calendar::*;
+pub use csv_export::*;
 pub use app::*;
@@ -0,0 +1,329 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::fmt;
+
+/// How often a recurring task repeats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Subset of an RFC 5545 RRULE: FREQ (DAILY/WEEKLY/MONTHLY), INTERVAL,
+/// BYDAY, UNTIL and COUNT. Occurrences are whole days; the task's own date
+/// is the series start (DTSTART) and only dates matching the rule are
+/// occurrences.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    /// Weekdays, each optionally with an ordinal within the month (MONTHLY only),
+    /// e.g. `(Some(-1), Fri)` for the last Friday
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub until: Option<NaiveDate>,
+    pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parse a rule such as `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20261231`
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let value = value.strip_prefix("RRULE:").unwrap_or(value);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut until = None;
+        let mut count = None;
+
+        for part in value.split(';').filter(|part| !part.trim().is_empty()) {
+            let (key, val) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid recurrence rule part: {}", part))?;
+            let val = val.trim();
+
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match val.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("Unsupported recurrence frequency: {}", val)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = val
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| format!("Invalid recurrence interval: {}", val))?
+                }
+                "BYDAY" => {
+                    by_day = val
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<Vec<_>, _>>()?
+                }
+                "UNTIL" => until = Some(parse_until(val)?),
+                "COUNT" => {
+                    count = Some(
+                        val.parse::<u32>()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| format!("Invalid recurrence count: {}", val))?,
+                    )
+                }
+                other => return Err(format!("Unsupported recurrence rule part: {}", other)),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| "Recurrence rule needs a FREQ".to_string())?;
+        if until.is_some() && count.is_some() {
+            return Err("Recurrence rule cannot have both UNTIL and COUNT".to_string());
+        }
+        if frequency != Frequency::Monthly && by_day.iter().any(|(ordinal, _)| ordinal.is_some()) {
+            return Err("Numbered BYDAY values are only supported for monthly rules".to_string());
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            until,
+            count,
+        })
+    }
+
+    /// Occurrence dates within `[from, to]` for a series starting on `start`
+    pub(crate) fn occurrences(&self, start: NaiveDate, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut emitted = 0u32;
+
+        for period in 0u32.. {
+            let Some(candidates) = self.period_dates(start, period) else {
+                break;
+            };
+            // Periods only move forward, so once one starts after the window we are done
+            if candidates.first > to {
+                break;
+            }
+
+            for date in candidates.dates {
+                if date < start {
+                    continue;
+                }
+                if date > to || self.until.is_some_and(|until| date > until) {
+                    return dates;
+                }
+                if self.count.is_some_and(|count| emitted >= count) {
+                    return dates;
+                }
+
+                emitted += 1;
+                if date >= from {
+                    dates.push(date);
+                }
+            }
+        }
+
+        dates
+    }
+
+    /// Whether `date` is an occurrence of a series starting on `start`
+    pub(crate) fn is_occurrence(&self, start: NaiveDate, date: NaiveDate) -> bool {
+        self.occurrences(start, date, date).contains(&date)
+    }
+
+    /// Candidate dates of the `period`-th day/week/month of the series, in order
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<PeriodDates> {
+        let step = period.checked_mul(self.interval)?;
+
+        match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_days(chrono::Days::new(step as u64))?;
+                let matches = self.by_day.is_empty()
+                    || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday());
+                Some(PeriodDates {
+                    first: date,
+                    dates: if matches { vec![date] } else { Vec::new() },
+                })
+            }
+            Frequency::Weekly => {
+                let week_start = start - chrono::Duration::days(start.weekday().num_days_from_monday() as i64);
+                let week_start = week_start.checked_add_days(chrono::Days::new(step as u64 * 7))?;
+
+                let mut offsets: Vec<u32> = if self.by_day.is_empty() {
+                    vec![start.weekday().num_days_from_monday()]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|(_, weekday)| weekday.num_days_from_monday())
+                        .collect()
+                };
+                offsets.sort_unstable();
+                offsets.dedup();
+
+                Some(PeriodDates {
+                    first: week_start,
+                    dates: offsets
+                        .into_iter()
+                        .map(|offset| week_start + chrono::Duration::days(offset as i64))
+                        .collect(),
+                })
+            }
+            Frequency::Monthly => {
+                let month_start = start
+                    .with_day(1)?
+                    .checked_add_months(chrono::Months::new(step))?;
+
+                let mut dates: Vec<NaiveDate> = if self.by_day.is_empty() {
+                    // Months without the start's day of month are skipped
+                    month_start.with_day(start.day()).into_iter().collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|(ordinal, weekday)| weekdays_in_month(month_start, *ordinal, *weekday))
+                        .collect()
+                };
+                dates.sort_unstable();
+                dates.dedup();
+
+                Some(PeriodDates {
+                    first: month_start,
+                    dates,
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|(ordinal, weekday)| {
+                    let code = weekday_code(*weekday);
+                    match ordinal {
+                        Some(ordinal) => format!("{}{}", ordinal, code),
+                        None => code.to_string(),
+                    }
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Dates generated for one day, week or month of a series
+struct PeriodDates {
+    /// First day of the period, used to stop once the window is passed
+    first: NaiveDate,
+    dates: Vec<NaiveDate>,
+}
+
+/// Every matching weekday in the month, or only the n-th (negative counts from the end)
+fn weekdays_in_month(month_start: NaiveDate, ordinal: Option<i32>, weekday: Weekday) -> Vec<NaiveDate> {
+    let all: Vec<NaiveDate> = month_start
+        .iter_days()
+        .take_while(|date| date.month() == month_start.month())
+        .filter(|date| date.weekday() == weekday)
+        .collect();
+
+    match ordinal {
+        None => all,
+        Some(n) if n > 0 => all.get(n as usize - 1).copied().into_iter().collect(),
+        Some(n) if n < 0 => all
+            .len()
+            .checked_sub(n.unsigned_abs() as usize)
+            .and_then(|index| all.get(index).copied())
+            .into_iter()
+            .collect(),
+        Some(_) => Vec::new(),
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<(Option<i32>, Weekday), String> {
+    let value = value.trim().to_ascii_uppercase();
+    // Weekday codes are ASCII; checking first keeps the split on a char boundary
+    if value.len() < 2 || !value.is_ascii() {
+        return Err(format!("Invalid BYDAY value: {}", value));
+    }
+
+    let (ordinal, code) = value.split_at(value.len() - 2);
+    let weekday = match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(format!("Invalid BYDAY value: {}", value)),
+    };
+
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        let n = ordinal
+            .trim_start_matches('+')
+            .parse::<i32>()
+            .ok()
+            .filter(|n| *n != 0 && n.abs() <= 5)
+            .ok_or_else(|| format!("Invalid BYDAY value: {}", value))?;
+        Some(n)
+    };
+
+    Ok((ordinal, weekday))
+}
+
+/// UNTIL as a date (`20261231`, `20261231T235959Z` or `2026-12-31`)
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    let date_part = value.split('T').next().unwrap_or(value);
+    NaiveDate::parse_from_str(date_part, "%Y%m%d")
+        .or_else(|_| NaiveDate::parse_from_str(date_part, "%Y-%m-%d"))
+        .map_err(|_| format!("Invalid recurrence end date: {}", value))
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_multi_byte_by_day() {
+        let error = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=€").unwrap_err();
+        assert_eq!(error, "Invalid BYDAY value: €");
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=1ÉMO").is_err());
+    }
+}
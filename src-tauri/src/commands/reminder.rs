@@ -0,0 +1,284 @@
+use crate::commands::clock::{get_user_setting, UserClock};
+use crate::commands::task::{expand_occurrences, get_task_by_id};
+use crate::db::Database;
+use crate::models::{ReminderStatus, Task, TaskCategory, TaskReminder};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+/// User setting holding how many minutes before a task starts its reminder fires
+pub(crate) const REMINDER_LEAD_MINUTES_KEY: &str = "reminder_lead_minutes";
+
+/// Lead time used when the user has not configured one
+pub(crate) const DEFAULT_REMINDER_LEAD_MINUTES: u32 = 10;
+
+/// Reminders missed while the app was closed are still delivered this long
+/// after the task started
+const MISSED_REMINDER_GRACE_MINUTES: i64 = 60;
+
+/// Snooze length when none is given
+const DEFAULT_SNOOZE_MINUTES: u32 = 5;
+
+const REMINDER_SELECT: &str =
+    "SELECT r.id, r.task_id, t.user_id, t.title, t.category, r.occurrence_date, r.due_at, r.status,
+            r.delivered_at, r.snoozed_until
+     FROM task_reminders r
+     JOIN tasks t ON t.id = r.task_id";
+
+/// Reminder rows (`r`, joined to `t`) whose task or occurrence is still open
+const OPEN_REMINDER_CONDITION: &str =
+    "((r.occurrence_date IS NULL AND COALESCE(t.completed, 0) = 0)
+      OR (r.occurrence_date IS NOT NULL AND NOT EXISTS(
+            SELECT 1 FROM task_occurrences o
+            WHERE o.task_id = r.task_id AND o.occurrence_date = r.occurrence_date
+              AND (o.completed = 1 OR o.skipped = 1))))";
+
+/// Get delivered and snoozed reminders that have not been dismissed and
+/// whose task is still open
+#[tauri::command]
+pub fn get_active_reminders(db: State<Database>, user_id: String) -> Result<Vec<TaskReminder>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND r.status != 'dismissed' AND {}
+             ORDER BY r.due_at ASC",
+            REMINDER_SELECT, OPEN_REMINDER_CONDITION
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reminders = stmt
+        .query_map(params![user_id], map_reminder)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(reminders)
+}
+
+/// Snooze a reminder so it fires again after `minutes` (default 5)
+#[tauri::command]
+pub fn snooze_reminder(
+    db: State<Database>,
+    reminder_id: String,
+    minutes: Option<u32>,
+) -> Result<TaskReminder, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let minutes = minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES);
+    if minutes == 0 || minutes > 24 * 60 {
+        return Err(format!("Snooze must be between 1 and 1440 minutes: {}", minutes));
+    }
+    let until = Utc::now() + chrono::Duration::minutes(minutes as i64);
+
+    update_reminder_status(&conn, &reminder_id, ReminderStatus::Snoozed, Some(until.to_rfc3339()))?;
+    get_reminder_by_id(&conn, &reminder_id)
+}
+
+/// Dismiss a reminder for good
+#[tauri::command]
+pub fn dismiss_reminder(db: State<Database>, reminder_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    update_reminder_status(&conn, &reminder_id, ReminderStatus::Dismissed, None)
+}
+
+/// Find reminders that should fire at `now`, record them as delivered and
+/// return them. Covers open Reminder/Event tasks (occurrences included)
+/// starting within each user's lead time, reminders missed by up to an hour,
+/// and snoozed reminders whose snooze has run out. A reminder already
+/// delivered or dismissed for the same task and start time is not repeated.
+pub(crate) fn deliver_due_reminders(
+    conn: &rusqlite::Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<TaskReminder>, String> {
+    let mut delivered = Vec::new();
+    let now_str = now.to_rfc3339();
+
+    // Snoozed reminders that are due again, unless the task was done meanwhile
+    let snoozed: Vec<String> = {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT r.id FROM task_reminders r
+                 JOIN tasks t ON t.id = r.task_id
+                 WHERE r.status = 'snoozed' AND r.snoozed_until <= ?1 AND {}",
+                OPEN_REMINDER_CONDITION
+            ))
+            .map_err(|e| e.to_string())?;
+        let ids = stmt
+            .query_map(params![now_str], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        ids
+    };
+    for reminder_id in snoozed {
+        conn.execute(
+            "UPDATE task_reminders SET status = 'delivered', delivered_at = ?1, snoozed_until = NULL,
+                    updated_at = datetime('now')
+             WHERE id = ?2",
+            params![now_str, reminder_id],
+        )
+        .map_err(|e| e.to_string())?;
+        delivered.push(get_reminder_by_id(conn, &reminder_id)?);
+    }
+
+    // Only Reminder/Event tasks near now are read: one-off tasks on days
+    // close enough for any zone, lead time and grace period, and series that
+    // have started by then
+    let today = now.date_naive();
+    let first_day = (today - chrono::Duration::days(2)).format("%Y-%m-%d").to_string();
+    let last_day = (today + chrono::Duration::days(2)).format("%Y-%m-%d").to_string();
+    let candidates: Vec<(String, String)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id FROM tasks
+                 WHERE category IN ('Reminder', 'Event') AND date >= ?1 AND date <= ?2
+                   AND recurrence IS NULL AND completed = 0
+                 UNION ALL
+                 SELECT id, user_id FROM tasks
+                 WHERE category IN ('Reminder', 'Event') AND date <= ?2 AND recurrence IS NOT NULL",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![first_day, last_day], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        rows
+    };
+
+    let grace = chrono::Duration::minutes(MISSED_REMINDER_GRACE_MINUTES);
+    let mut users: HashMap<String, (UserClock, chrono::Duration)> = HashMap::new();
+
+    for (task_id, user_id) in candidates {
+        if !users.contains_key(&user_id) {
+            let clock = UserClock::load(conn, &user_id)?;
+            let lead = chrono::Duration::minutes(reminder_lead_minutes(conn, &user_id)? as i64);
+            users.insert(user_id.clone(), (clock, lead));
+        }
+        let (clock, lead) = &users[&user_id];
+
+        let task = get_task_by_id(conn, &task_id)?;
+        let tasks = if task.recurrence.is_some() {
+            // Wide enough for any lead time and the grace period around today
+            let day = clock.date_of(now);
+            expand_occurrences(conn, &task, day - chrono::Duration::days(1), day + chrono::Duration::days(2))?
+        } else {
+            vec![task]
+        };
+
+        for task in tasks.iter().filter(|task| wants_reminder(task)) {
+            let Some(due) = task_start(clock, task) else {
+                continue;
+            };
+            if now < due - *lead || now > due + grace {
+                continue;
+            }
+
+            let due_at = due.to_rfc3339();
+            let existing: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM task_reminders WHERE task_id = ?1 AND due_at = ?2",
+                    params![task.id, due_at],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if existing.is_some() {
+                continue;
+            }
+
+            let id = Uuid::new_v4().to_string();
+            conn.execute(
+                "INSERT INTO task_reminders (id, task_id, occurrence_date, due_at, status, delivered_at)
+                 VALUES (?1, ?2, ?3, ?4, 'delivered', ?5)",
+                params![id, task.id, task.occurrence_date, due_at, now_str],
+            )
+            .map_err(|e| e.to_string())?;
+            delivered.push(get_reminder_by_id(conn, &id)?);
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// Validate a reminder lead time setting value (minutes, up to a day)
+pub(crate) fn parse_reminder_lead_minutes(value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|minutes| *minutes <= 24 * 60)
+        .ok_or_else(|| format!("Reminder lead time must be between 0 and 1440 minutes: {}", value))
+}
+
+/// The user's reminder lead time in minutes
+pub(crate) fn reminder_lead_minutes(conn: &rusqlite::Connection, user_id: &str) -> Result<u32, String> {
+    Ok(get_user_setting(conn, user_id, REMINDER_LEAD_MINUTES_KEY)?
+        .and_then(|value| parse_reminder_lead_minutes(&value).ok())
+        .unwrap_or(DEFAULT_REMINDER_LEAD_MINUTES))
+}
+
+fn wants_reminder(task: &Task) -> bool {
+    !task.completed && matches!(task.category, TaskCategory::Reminder | TaskCategory::Event)
+}
+
+/// Start of a task (its date and start time) in the user's zone
+fn task_start(clock: &UserClock, task: &Task) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(&task.date, "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(&task.start_time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&task.start_time, "%H:%M:%S"))
+        .ok()?;
+    Some(clock.local_instant(date.and_time(time)))
+}
+
+fn update_reminder_status(
+    conn: &rusqlite::Connection,
+    reminder_id: &str,
+    status: ReminderStatus,
+    snoozed_until: Option<String>,
+) -> Result<(), String> {
+    let updated = conn
+        .execute(
+            "UPDATE task_reminders SET status = ?1, snoozed_until = ?2, updated_at = datetime('now')
+             WHERE id = ?3",
+            params![status.as_str(), snoozed_until, reminder_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err("Reminder not found".to_string());
+    }
+    Ok(())
+}
+
+fn get_reminder_by_id(conn: &rusqlite::Connection, reminder_id: &str) -> Result<TaskReminder, String> {
+    conn.query_row(
+        &format!("{} WHERE r.id = ?1", REMINDER_SELECT),
+        params![reminder_id],
+        map_reminder,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Reminder not found".to_string())
+}
+
+fn map_reminder(row: &rusqlite::Row) -> rusqlite::Result<TaskReminder> {
+    let category: String = row.get(4)?;
+    let status: String = row.get(7)?;
+    Ok(TaskReminder {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        user_id: row.get(2)?,
+        title: row.get(3)?,
+        category: TaskCategory::from_str(&category).unwrap_or(TaskCategory::Reminder),
+        occurrence_date: row.get(5)?,
+        due_at: row.get(6)?,
+        status: ReminderStatus::from_str(&status).unwrap_or(ReminderStatus::Delivered),
+        delivered_at: row.get(8)?,
+        snoozed_until: row.get(9)?,
+    })
+}
@@ -0,0 +1,253 @@
+use crate::commands::clock::UserClock;
+use crate::commands::task::{get_task_by_id, map_task, TASK_SELECT};
+use crate::db::Database;
+use crate::models::{DateChangeReason, PostponementReport, Task, TaskDateChange, TaskPostponement};
+use chrono::NaiveDate;
+use rusqlite::params;
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+/// Get unfinished one-off tasks dated before the user's today, oldest first.
+/// Recurring tasks are left out since their next occurrence comes anyway.
+#[tauri::command]
+pub fn get_overdue_tasks(db: State<Database>, user_id: String) -> Result<Vec<Task>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let today = UserClock::load(&conn, &user_id)?.today();
+    load_overdue_tasks(&conn, &user_id, today)
+}
+
+/// Move unfinished tasks to today, recording where they came from. Without
+/// `task_ids` every overdue task is rolled over; listed tasks must be overdue.
+#[tauri::command]
+pub fn roll_over_tasks(
+    db: State<Database>,
+    user_id: String,
+    task_ids: Option<Vec<String>>,
+) -> Result<Vec<Task>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let today = UserClock::load(&conn, &user_id)?.today();
+    let overdue = load_overdue_tasks(&conn, &user_id, today)?;
+
+    let tasks: Vec<Task> = match task_ids {
+        Some(task_ids) => task_ids
+            .iter()
+            .map(|task_id| {
+                overdue
+                    .iter()
+                    .find(|task| task.id == *task_id)
+                    .cloned()
+                    .ok_or_else(|| format!("Task {} is not an unfinished overdue task", task_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => overdue,
+    };
+
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let mut rolled = Vec::new();
+    for task in tasks {
+        if rolled.iter().any(|done: &Task| done.id == task.id) {
+            continue;
+        }
+
+        record_task_date_change(&conn, &task.id, &task.date, &today_str, DateChangeReason::Rollover)?;
+        conn.execute(
+            "UPDATE tasks SET date = ?1 WHERE id = ?2",
+            params![today_str, task.id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        rolled.push(get_task_by_id(&conn, &task.id)?);
+    }
+
+    Ok(rolled)
+}
+
+/// Report how often one-off tasks originally planned between `start_date`
+/// and `end_date` (inclusive, both optional) were pushed to a later day
+#[tauri::command]
+pub fn get_postponement_report(
+    db: State<Database>,
+    user_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<PostponementReport, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND t.recurrence IS NULL",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks: Vec<Task> = stmt
+        .query_map(params![user_id], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|task| {
+            let planned = task.original_date.as_deref().unwrap_or(&task.date);
+            start_date.as_deref().map_or(true, |start| planned >= start)
+                && end_date.as_deref().map_or(true, |end| planned <= end)
+        })
+        .collect();
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.task_id, COUNT(*) FROM task_date_changes c
+             JOIN tasks t ON t.id = c.task_id
+             WHERE t.user_id = ?1 AND c.reason = ?2 AND c.to_date > c.from_date
+             GROUP BY c.task_id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rollovers: HashMap<String, i64> = stmt
+        .query_map(params![user_id, DateChangeReason::Rollover.as_str()], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let task_count = tasks.len() as i64;
+    let mut postponement_count = 0;
+    let mut rollover_count = 0;
+    let mut postponed = Vec::new();
+    for task in tasks {
+        if task.postpone_count == 0 {
+            continue;
+        }
+        postponement_count += task.postpone_count;
+        rollover_count += rollovers.get(&task.id).copied().unwrap_or(0);
+
+        let original_date = task.original_date.clone().unwrap_or_else(|| task.date.clone());
+        let days_postponed = match (parse_date(&original_date), parse_date(&task.date)) {
+            (Some(original), Some(current)) => (current - original).num_days(),
+            _ => 0,
+        };
+
+        postponed.push(TaskPostponement {
+            task_id: task.id,
+            title: task.title,
+            category: task.category,
+            original_date,
+            date: task.date,
+            completed: task.completed,
+            times_postponed: task.postpone_count,
+            days_postponed,
+        });
+    }
+
+    postponed.sort_by(|a, b| {
+        b.times_postponed
+            .cmp(&a.times_postponed)
+            .then(b.days_postponed.cmp(&a.days_postponed))
+    });
+
+    let postponed_task_count = postponed.len() as i64;
+    let postponement_rate = if task_count > 0 {
+        postponed_task_count as f64 / task_count as f64
+    } else {
+        0.0
+    };
+    let average_days_postponed = if postponed_task_count > 0 {
+        postponed.iter().map(|task| task.days_postponed).sum::<i64>() as f64 / postponed_task_count as f64
+    } else {
+        0.0
+    };
+
+    Ok(PostponementReport {
+        task_count,
+        postponed_task_count,
+        postponement_count,
+        rollover_count,
+        postponement_rate,
+        average_days_postponed,
+        tasks: postponed,
+    })
+}
+
+/// Record that a task moved from one date to another
+pub(crate) fn record_task_date_change(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    from_date: &str,
+    to_date: &str,
+    reason: DateChangeReason,
+) -> Result<(), String> {
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO task_date_changes (id, task_id, from_date, to_date, reason, changed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![id, task_id, from_date, to_date, reason.as_str(), now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Load the date history of all of a user's tasks
+pub(crate) fn load_user_task_date_changes(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<TaskDateChange>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.id, c.task_id, c.from_date, c.to_date, c.reason, c.changed_at
+             FROM task_date_changes c
+             JOIN tasks t ON t.id = c.task_id
+             WHERE t.user_id = ?1
+             ORDER BY c.changed_at ASC, c.rowid ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let changes = stmt
+        .query_map(params![user_id], |row| {
+            let reason: String = row.get(4)?;
+            Ok(TaskDateChange {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                from_date: row.get(2)?,
+                to_date: row.get(3)?,
+                reason: DateChangeReason::from_str(&reason).unwrap_or(DateChangeReason::Reschedule),
+                changed_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(changes)
+}
+
+fn load_overdue_tasks(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    today: NaiveDate,
+) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND t.recurrence IS NULL AND COALESCE(t.completed, 0) = 0 AND t.date < ?2
+             ORDER BY t.date ASC, t.start_time ASC",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks = stmt
+        .query_map(params![user_id, today.format("%Y-%m-%d").to_string()], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(tasks)
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
@@ -1,6 +1,20 @@
+use crate::commands::achievement::revoke_unearned_achievements;
+use crate::commands::clock::UserClock;
+use crate::commands::goal::goal_streaks;
+use crate::commands::subject::resolve_session_subject;
+use crate::commands::task::count_completed_tasks;
+use crate::commands::streak::{
+    active_dates, apply_streak_freezes, available_freezes, current_streak, frozen_dates,
+    longest_streak,
+};
 use crate::db::Database;
-use crate::models::{CreateFocusSessionRequest, DailyStats, FocusSession, HeatmapData, UserStats};
+use crate::models::{
+    CategoryStats, CreateFocusSessionRequest, DailyStats, FocusSession, HeatmapData,
+    SessionSegment, StatsGranularity, UpdateFocusSessionRequest, UserStats,
+};
+use chrono::{Datelike, NaiveDate};
 use rusqlite::params;
+use std::collections::BTreeMap;
 use tauri::State;
 use uuid::Uuid;
 
@@ -11,29 +25,189 @@ pub fn create_focus_session(
     request: CreateFocusSessionRequest,
 ) -> Result<FocusSession, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    insert_focus_session(&conn, request)
+}
+
+/// Log a session after the fact, validating it and rejecting overlaps
+/// with existing sessions unless `allow_overlap` is set
+#[tauri::command]
+pub fn log_manual_session(
+    db: State<Database>,
+    request: CreateFocusSessionRequest,
+    allow_overlap: Option<bool>,
+) -> Result<FocusSession, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    validate_manual_session(&request)?;
+
+    if !allow_overlap.unwrap_or(false) {
+        let overlapping = find_overlapping_sessions_internal(
+            &conn,
+            &request.user_id,
+            &request.started_at,
+            &request.ended_at,
+            None,
+        )?;
+
+        if !overlapping.is_empty() {
+            let ids: Vec<&str> = overlapping.iter().map(|s| s.id.as_str()).collect();
+            return Err(format!(
+                "Session overlaps {} existing session(s): {}",
+                overlapping.len(),
+                ids.join(", ")
+            ));
+        }
+    }
+
+    insert_focus_session(&conn, request)
+}
+
+/// Get existing sessions that overlap the given interval
+#[tauri::command]
+pub fn find_overlapping_sessions(
+    db: State<Database>,
+    user_id: String,
+    started_at: String,
+    ended_at: String,
+    exclude_session_id: Option<String>,
+) -> Result<Vec<FocusSession>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    find_overlapping_sessions_internal(
+        &conn,
+        &user_id,
+        &started_at,
+        &ended_at,
+        exclude_session_id.as_deref(),
+    )
+}
+
+pub(crate) fn validate_manual_session(request: &CreateFocusSessionRequest) -> Result<(), String> {
+    let started = parse_session_timestamp(&request.started_at)?;
+    let ended = parse_session_timestamp(&request.ended_at)?;
+
+    if ended <= started {
+        return Err("Session must end after it starts".to_string());
+    }
+    if ended > chrono::Utc::now() {
+        return Err("Session cannot end in the future".to_string());
+    }
+
+    let interval_seconds = (ended - started).num_seconds();
+    if request.duration_seconds <= 0 || request.duration_seconds > interval_seconds {
+        return Err(format!(
+            "Session duration must be between 1 and {} seconds",
+            interval_seconds
+        ));
+    }
+
+    if let Some(ref segments) = request.segments {
+        validate_session_segments(segments)?;
+
+        let mut focused_seconds = 0;
+        for segment in segments {
+            let segment_start = parse_session_timestamp(&segment.started_at)?;
+            let segment_end = parse_session_timestamp(&segment.ended_at)?;
+            if segment_start < started || segment_end > ended {
+                return Err("Session segments must lie within the session".to_string());
+            }
+            focused_seconds += segment.duration_seconds;
+        }
+
+        if !segments.is_empty() && focused_seconds != request.duration_seconds {
+            return Err("Session duration does not match its segments".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn find_overlapping_sessions_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    started_at: &str,
+    ended_at: &str,
+    exclude_session_id: Option<&str>,
+) -> Result<Vec<FocusSession>, String> {
+    let started = parse_session_timestamp(started_at)?;
+    let ended = parse_session_timestamp(ended_at)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} 
+             FROM focus_sessions 
+             WHERE user_id = ?1 AND id IS NOT ?2 
+             ORDER BY started_at ASC",
+            SESSION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let sessions = stmt
+        .query_map(params![user_id, exclude_session_id], map_focus_session)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // Timestamps may carry different offsets, so compare parsed instants
+    let mut overlapping: Vec<FocusSession> = sessions
+        .into_iter()
+        .filter(|session| {
+            match (
+                parse_session_timestamp(&session.started_at),
+                parse_session_timestamp(&session.ended_at),
+            ) {
+                (Ok(other_start), Ok(other_end)) => started < other_end && ended > other_start,
+                _ => false,
+            }
+        })
+        .collect();
+
+    attach_session_segments(conn, &mut overlapping)?;
+
+    Ok(overlapping)
+}
+
+/// Insert a finished focus session and fold it into daily stats
+pub(crate) fn insert_focus_session(
+    conn: &rusqlite::Connection,
+    request: CreateFocusSessionRequest,
+) -> Result<FocusSession, String> {
+    parse_session_timestamp(&request.started_at)?;
+    parse_session_timestamp(&request.ended_at)?;
+    let segments = request.segments.unwrap_or_default();
+    validate_session_segments(&segments)?;
+
+    let (subject_id, category) = resolve_session_subject(
+        conn,
+        &request.user_id,
+        request.subject_id.as_deref(),
+        request.category.as_deref(),
+    )?;
+    if let Some(ref task_id) = request.task_id {
+        validate_session_task(conn, &request.user_id, task_id)?;
+    }
 
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     conn.execute(
-        "INSERT INTO focus_sessions (id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        "INSERT INTO focus_sessions (id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at, subject_id, task_id) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         params![
             id,
             request.user_id,
             request.duration_seconds,
             request.started_at,
             request.ended_at,
-            request.category,
+            category,
             request.notes,
-            now
+            now,
+            subject_id,
+            request.task_id
         ],
     )
     .map_err(|e| e.to_string())?;
 
-    // Update daily stats (use local date based on session start)
-    let session_date = local_date_from_iso(&request.started_at)?;
-    update_daily_stats(&conn, &request.user_id, &session_date, request.duration_seconds)?;
+    insert_session_segments(conn, &id, &segments)?;
 
     let session = FocusSession {
         id,
@@ -41,47 +215,569 @@ pub fn create_focus_session(
         duration_seconds: request.duration_seconds,
         started_at: request.started_at,
         ended_at: request.ended_at,
-        category: request.category,
+        category,
         notes: request.notes,
         created_at: now,
+        segments,
+        subject_id,
+        task_id: request.task_id,
     };
 
+    // Update daily stats, spreading the session over the user days it covers
+    let clock = UserClock::load(conn, &session.user_id)?;
+    let allocation = allocate_session_by_day(&clock, &session)?;
+    update_daily_stats(conn, &session.user_id, &allocation)?;
+    apply_streak_freezes(conn, &session.user_id)?;
+
     Ok(session)
 }
 
-fn local_date_from_iso(value: &str) -> Result<String, String> {
-    if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(value) {
-        let local = parsed.with_timezone(&chrono::Local);
-        return Ok(local.format("%Y-%m-%d").to_string());
+/// Update a focus session, keeping daily stats and achievements in sync
+#[tauri::command]
+pub fn update_focus_session(
+    db: State<Database>,
+    session_id: String,
+    request: UpdateFocusSessionRequest,
+) -> Result<FocusSession, String> {
+    let mut conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    // The edit and the stats it affects are saved together
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let updated = update_focus_session_internal(&tx, &session_id, request)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(updated)
+}
+
+fn update_focus_session_internal(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+    request: UpdateFocusSessionRequest,
+) -> Result<FocusSession, String> {
+    let existing = get_focus_session_by_id(conn, session_id)?;
+
+    // Edited timing must pass the same checks as a manually logged session
+    let timing_changed = request.duration_seconds.is_some()
+        || request.started_at.is_some()
+        || request.ended_at.is_some()
+        || request.segments.is_some();
+    if timing_changed {
+        validate_manual_session(&merge_session_update(&existing, &request))?;
+    }
+
+    // Changing either the subject or the category re-resolves both
+    let subject = if request.subject_id.is_some() || request.category.is_some() {
+        Some(resolve_session_subject(
+            conn,
+            &existing.user_id,
+            request.subject_id.as_deref(),
+            request.category.as_deref(),
+        )?)
+    } else {
+        None
+    };
+
+    let task_id = request.task_id.as_deref().map(|task_id| match task_id {
+        "" => None,
+        task_id => Some(task_id.to_string()),
+    });
+    if let Some(Some(ref task_id)) = task_id {
+        validate_session_task(conn, &existing.user_id, task_id)?;
+    }
+
+    // Build dynamic update query
+    let mut updates = Vec::new();
+    let mut param_idx = 1;
+
+    if request.duration_seconds.is_some() {
+        updates.push(format!("duration_seconds = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.started_at.is_some() {
+        updates.push(format!("started_at = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.ended_at.is_some() {
+        updates.push(format!("ended_at = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if subject.is_some() {
+        updates.push(format!("subject_id = ?{}", param_idx));
+        param_idx += 1;
+        updates.push(format!("category = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.notes.is_some() {
+        updates.push(format!("notes = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if task_id.is_some() {
+        updates.push(format!("task_id = ?{}", param_idx));
+        param_idx += 1;
+    }
+
+    if !updates.is_empty() {
+        let query = format!(
+            "UPDATE focus_sessions SET {} WHERE id = ?{}",
+            updates.join(", "),
+            param_idx
+        );
+
+        // Build params vector
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(duration_seconds) = request.duration_seconds {
+            params_vec.push(Box::new(duration_seconds));
+        }
+        if let Some(ref started_at) = request.started_at {
+            params_vec.push(Box::new(started_at.clone()));
+        }
+        if let Some(ref ended_at) = request.ended_at {
+            params_vec.push(Box::new(ended_at.clone()));
+        }
+        if let Some((ref subject_id, ref category)) = subject {
+            params_vec.push(Box::new(subject_id.clone()));
+            params_vec.push(Box::new(category.clone()));
+        }
+        if let Some(ref notes) = request.notes {
+            params_vec.push(Box::new(notes.clone()));
+        }
+        if let Some(ref task_id) = task_id {
+            params_vec.push(Box::new(task_id.clone()));
+        }
+        params_vec.push(Box::new(session_id.to_string()));
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+        conn.execute(&query, params_refs.as_slice())
+            .map_err(|e| e.to_string())?;
+    }
+
+    // Old segments no longer describe a session whose interval moved
+    let interval_changed = request.started_at.is_some() || request.ended_at.is_some();
+    if request.segments.is_some() || interval_changed {
+        conn.execute(
+            "DELETE FROM session_segments WHERE session_id = ?1",
+            params![session_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    if let Some(ref segments) = request.segments {
+        insert_session_segments(conn, session_id, segments)?;
+    }
+
+    let updated = get_focus_session_by_id(conn, session_id)?;
+    refresh_session_stats(conn, &[&existing, &updated])?;
+
+    Ok(updated)
+}
+
+/// The session an update would leave behind, as a manual session request
+fn merge_session_update(
+    existing: &FocusSession,
+    request: &UpdateFocusSessionRequest,
+) -> CreateFocusSessionRequest {
+    // Moving the interval drops the old segments
+    let interval_changed = request.started_at.is_some() || request.ended_at.is_some();
+    let segments = match request.segments {
+        Some(ref segments) => Some(segments.clone()),
+        None if interval_changed => None,
+        None => Some(existing.segments.clone()),
+    };
+
+    CreateFocusSessionRequest {
+        user_id: existing.user_id.clone(),
+        duration_seconds: request.duration_seconds.unwrap_or(existing.duration_seconds),
+        started_at: request.started_at.clone().unwrap_or_else(|| existing.started_at.clone()),
+        ended_at: request.ended_at.clone().unwrap_or_else(|| existing.ended_at.clone()),
+        category: existing.category.clone(),
+        notes: existing.notes.clone(),
+        segments,
+        subject_id: existing.subject_id.clone(),
+        task_id: existing.task_id.clone(),
+    }
+}
+
+/// Delete a focus session, keeping daily stats and achievements in sync
+#[tauri::command]
+pub fn delete_focus_session(db: State<Database>, session_id: String) -> Result<(), String> {
+    let mut conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let existing = get_focus_session_by_id(&tx, &session_id)?;
+
+    tx.execute("DELETE FROM focus_sessions WHERE id = ?1", params![session_id])
+        .map_err(|e| e.to_string())?;
+
+    refresh_session_stats(&tx, &[&existing])?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Recompute the stats days the given session versions count toward, settle
+/// streak freezes and drop achievements the user no longer qualifies for
+fn refresh_session_stats(
+    conn: &rusqlite::Connection,
+    sessions: &[&FocusSession],
+) -> Result<(), String> {
+    for session in sessions {
+        let clock = UserClock::load(conn, &session.user_id)?;
+        let allocation = allocate_session_by_day(&clock, session)?;
+        let first = allocation.seconds_by_day.keys().next();
+        let last = allocation.seconds_by_day.keys().next_back();
+        if let (Some(first), Some(last)) = (first, last) {
+            rebuild_daily_stats_internal(conn, &session.user_id, Some(first), Some(last))?;
+        }
+    }
+
+    if let Some(session) = sessions.first() {
+        apply_streak_freezes(conn, &session.user_id)?;
+        let revoked = revoke_unearned_achievements(conn, &session.user_id)?;
+        if !revoked.is_empty() {
+            log::info!("Revoked achievements {:?} for user {}", revoked, session.user_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Make sure a session is linked to one of its own user's tasks
+pub(crate) fn validate_session_task(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    task_id: &str,
+) -> Result<(), String> {
+    let exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1 AND user_id = ?2)",
+            params![task_id, user_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if !exists {
+        return Err("Task not found".to_string());
+    }
+
+    Ok(())
+}
+
+/// Columns read by `map_focus_session`, in order
+pub(crate) const SESSION_COLUMNS: &str =
+    "id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at, subject_id, task_id";
+
+/// Map a row selected with `SESSION_COLUMNS`; segments are attached separately
+pub(crate) fn map_focus_session(row: &rusqlite::Row) -> rusqlite::Result<FocusSession> {
+    Ok(FocusSession {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        duration_seconds: row.get(2)?,
+        started_at: row.get(3)?,
+        ended_at: row.get(4)?,
+        category: row.get(5)?,
+        notes: row.get(6)?,
+        created_at: row.get(7)?,
+        segments: Vec::new(),
+        subject_id: row.get(8)?,
+        task_id: row.get(9)?,
+    })
+}
+
+/// Helper function to get a focus session by ID
+fn get_focus_session_by_id(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+) -> Result<FocusSession, String> {
+    let mut session = conn
+        .query_row(
+            &format!("SELECT {} FROM focus_sessions WHERE id = ?1", SESSION_COLUMNS),
+            params![session_id],
+            map_focus_session,
+        )
+        .map_err(|e| e.to_string())?;
+
+    attach_session_segments(conn, std::slice::from_mut(&mut session))?;
+
+    Ok(session)
+}
+
+/// Store the focused stretches of a session in order
+pub(crate) fn insert_session_segments(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+    segments: &[SessionSegment],
+) -> Result<(), String> {
+    for (position, segment) in segments.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO session_segments (id, session_id, position, started_at, ended_at, duration_seconds) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                session_id,
+                position as i64,
+                segment.started_at,
+                segment.ended_at,
+                segment.duration_seconds
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn validate_session_segments(segments: &[SessionSegment]) -> Result<(), String> {
+    for segment in segments {
+        let started = parse_session_timestamp(&segment.started_at)?;
+        let ended = parse_session_timestamp(&segment.ended_at)?;
+        if ended < started || segment.duration_seconds < 0 {
+            return Err("Invalid session segment".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Fill in the segments of already loaded sessions
+pub(crate) fn attach_session_segments(
+    conn: &rusqlite::Connection,
+    sessions: &mut [FocusSession],
+) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT started_at, ended_at, duration_seconds 
+             FROM session_segments 
+             WHERE session_id = ?1 
+             ORDER BY position ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    for session in sessions.iter_mut() {
+        session.segments = stmt
+            .query_map(params![session.id], |row| {
+                Ok(SessionSegment {
+                    started_at: row.get(0)?,
+                    ended_at: row.get(1)?,
+                    duration_seconds: row.get(2)?,
+                })
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn parse_session_timestamp(value: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&chrono::Utc))
+        .map_err(|_| "Invalid session timestamp".to_string())
+}
+
+/// How a session's focused time is attributed to user days
+pub(crate) struct DayAllocation {
+    /// Day the session started on; the session is counted there
+    pub start_date: String,
+    /// Focused seconds per day (YYYY-MM-DD), always including `start_date`
+    pub seconds_by_day: BTreeMap<String, i64>,
+}
+
+/// Split a session's focused seconds across the user days it spans.
+/// Segments are used when present; otherwise the focused time is spread
+/// evenly over the session's wall-clock interval.
+pub(crate) fn allocate_session_by_day(
+    clock: &UserClock,
+    session: &FocusSession,
+) -> Result<DayAllocation, String> {
+    let start_date = clock.date_from_iso(&session.started_at)?;
+
+    let intervals: Result<Vec<_>, String> = if session.segments.is_empty() {
+        vec![(&session.started_at, &session.ended_at)]
+    } else {
+        session
+            .segments
+            .iter()
+            .map(|segment| (&segment.started_at, &segment.ended_at))
+            .collect()
+    }
+    .into_iter()
+    .map(|(start, end)| Ok((parse_session_timestamp(start)?, parse_session_timestamp(end)?)))
+    .collect();
+
+    let mut wall_by_day: BTreeMap<String, i64> = BTreeMap::new();
+    if let Ok(intervals) = intervals {
+        for (start, end) in intervals {
+            for (date, seconds) in clock.split_by_day(start, end) {
+                *wall_by_day.entry(date.format("%Y-%m-%d").to_string()).or_insert(0) += seconds;
+            }
+        }
     }
 
-    if value.len() >= 10 {
-        return Ok(value[..10].to_string());
+    let wall_seconds: i64 = wall_by_day.values().sum();
+    let mut seconds_by_day = BTreeMap::new();
+
+    if wall_seconds <= 0 {
+        // Legacy or instantaneous sessions: everything counts toward the start day
+        seconds_by_day.insert(start_date.clone(), session.duration_seconds);
+    } else {
+        // Scale wall-clock time to focused time; the last day takes the rounding remainder
+        let mut remaining = session.duration_seconds;
+        let day_count = wall_by_day.len();
+        for (index, (date, wall)) in wall_by_day.into_iter().enumerate() {
+            let share = if index + 1 == day_count {
+                remaining
+            } else {
+                (wall as i128 * session.duration_seconds as i128 / wall_seconds as i128) as i64
+            };
+            remaining -= share;
+            seconds_by_day.insert(date, share);
+        }
     }
 
-    Err("Invalid session timestamp".to_string())
+    seconds_by_day.entry(start_date.clone()).or_insert(0);
+
+    Ok(DayAllocation {
+        start_date,
+        seconds_by_day,
+    })
 }
 
-/// Update daily stats when a session is created
+/// Add a new session's allocation to the daily stats cache
 fn update_daily_stats(
     conn: &rusqlite::Connection,
     user_id: &str,
-    date: &str,
-    duration_seconds: i64,
+    allocation: &DayAllocation,
 ) -> Result<(), String> {
-    let stats_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    for (date, seconds) in &allocation.seconds_by_day {
+        let stats_id = Uuid::new_v4().to_string();
+        let session_count = if *date == allocation.start_date { 1 } else { 0 };
+
+        conn.execute(
+            "INSERT INTO daily_stats (id, user_id, date, total_focus_seconds, session_count, created_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(user_id, date) DO UPDATE SET 
+             total_focus_seconds = total_focus_seconds + ?4,
+             session_count = session_count + ?5",
+            params![stats_id, user_id, date, seconds, session_count, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Recompute cached daily stats from focus sessions, optionally for a date range
+#[tauri::command]
+pub fn rebuild_daily_stats(
+    db: State<Database>,
+    user_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    rebuild_daily_stats_internal(&conn, &user_id, start_date.as_deref(), end_date.as_deref())
+}
+
+/// Replace the daily stats rows in range with totals derived from focus sessions
+pub(crate) fn rebuild_daily_stats_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<(), String> {
+    let sessions = load_user_sessions(conn, user_id)?;
+
+    let in_range = |date: &str| {
+        !start_date.is_some_and(|start| date < start) && !end_date.is_some_and(|end| date > end)
+    };
+
+    let clock = UserClock::load(conn, user_id)?;
+    let mut totals: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    for session in &sessions {
+        let allocation = allocate_session_by_day(&clock, session)?;
+        for (date, seconds) in allocation.seconds_by_day {
+            if in_range(&date) {
+                let counted = if date == allocation.start_date { 1 } else { 0 };
+                let entry = totals.entry(date).or_insert((0, 0));
+                entry.0 += seconds;
+                entry.1 += counted;
+            }
+        }
+    }
+
     conn.execute(
-        "INSERT INTO daily_stats (id, user_id, date, total_focus_seconds, session_count, created_at) 
-         VALUES (?1, ?2, ?3, ?4, 1, ?5)
-         ON CONFLICT(user_id, date) DO UPDATE SET 
-         total_focus_seconds = total_focus_seconds + ?4,
-         session_count = session_count + 1",
-        params![stats_id, user_id, date, duration_seconds, now],
+        "DELETE FROM daily_stats 
+         WHERE user_id = ?1 AND (?2 IS NULL OR date >= ?2) AND (?3 IS NULL OR date <= ?3)",
+        params![user_id, start_date, end_date],
     )
     .map_err(|e| e.to_string())?;
 
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for (date, (total_focus_seconds, session_count)) in totals {
+        conn.execute(
+            "INSERT INTO daily_stats (id, user_id, date, total_focus_seconds, session_count, created_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                user_id,
+                date,
+                total_focus_seconds,
+                session_count,
+                now
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Load all of a user's sessions (with segments), oldest first
+pub(crate) fn load_user_sessions(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<FocusSession>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} 
+             FROM focus_sessions 
+             WHERE user_id = ?1 
+             ORDER BY started_at ASC",
+            SESSION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let mut sessions = stmt
+        .query_map(params![user_id], map_focus_session)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    attach_session_segments(conn, &mut sessions)?;
+
+    Ok(sessions)
+}
+
+/// Rebuild the daily stats cache for every user
+pub(crate) fn rebuild_all_daily_stats(conn: &rusqlite::Connection) -> Result<(), String> {
+    let user_ids: Vec<String> = {
+        let mut stmt = conn
+            .prepare("SELECT id FROM users")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    for user_id in user_ids {
+        rebuild_daily_stats_internal(conn, &user_id, None, None)?;
+    }
+
     Ok(())
 }
 
@@ -96,32 +792,24 @@ pub fn get_focus_sessions(
     let limit = limit.unwrap_or(100);
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, user_id, duration_seconds, started_at, ended_at, category, notes, created_at 
+        .prepare(&format!(
+            "SELECT {} 
              FROM focus_sessions 
              WHERE user_id = ?1 
              ORDER BY started_at DESC 
              LIMIT ?2",
-        )
+            SESSION_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
-    let sessions = stmt
-        .query_map(params![user_id, limit], |row| {
-            Ok(FocusSession {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                duration_seconds: row.get(2)?,
-                started_at: row.get(3)?,
-                ended_at: row.get(4)?,
-                category: row.get(5)?,
-                notes: row.get(6)?,
-                created_at: row.get(7)?,
-            })
-        })
+    let mut sessions = stmt
+        .query_map(params![user_id, limit], map_focus_session)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
 
+    attach_session_segments(&conn, &mut sessions)?;
+
     Ok(sessions)
 }
 
@@ -159,23 +847,105 @@ pub fn get_daily_stats(
     Ok(stats)
 }
 
+/// Get focus time per category, grouped by day, week or month, for a date range
+#[tauri::command]
+pub fn get_category_stats(
+    db: State<Database>,
+    user_id: String,
+    start_date: String,
+    end_date: String,
+    granularity: Option<StatsGranularity>,
+) -> Result<Vec<CategoryStats>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let granularity = granularity.unwrap_or(StatsGranularity::Day);
+
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+
+    // Keyed by period start, then subject, so results come out in order
+    type CategoryKey = (NaiveDate, Option<String>, Option<String>);
+    let mut totals: BTreeMap<CategoryKey, (i64, i64)> = BTreeMap::new();
+
+    let clock = UserClock::load(&conn, &user_id)?;
+    for session in load_user_sessions(&conn, &user_id)? {
+        let allocation = allocate_session_by_day(&clock, &session)?;
+        for (date, seconds) in &allocation.seconds_by_day {
+            let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+            if day < start || day > end {
+                continue;
+            }
+
+            let key = (
+                granularity_start(granularity, day),
+                session.subject_id.clone(),
+                session.category.clone(),
+            );
+            let entry = totals.entry(key).or_insert((0, 0));
+            entry.0 += seconds;
+            if *date == allocation.start_date {
+                entry.1 += 1;
+            }
+        }
+    }
+
+    let stats = totals
+        .into_iter()
+        .map(|((period_start, subject_id, category), (total_focus_seconds, session_count))| {
+            CategoryStats {
+                period: granularity_label(granularity, period_start),
+                period_start: period_start.format("%Y-%m-%d").to_string(),
+                subject_id,
+                category,
+                total_focus_seconds,
+                session_count,
+            }
+        })
+        .collect();
+
+    Ok(stats)
+}
+
+/// First day of the day, ISO week or month containing `date`
+pub(crate) fn granularity_start(granularity: StatsGranularity, date: NaiveDate) -> NaiveDate {
+    match granularity {
+        StatsGranularity::Day => date,
+        StatsGranularity::Week => {
+            date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+        }
+        StatsGranularity::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+pub(crate) fn granularity_label(granularity: StatsGranularity, start: NaiveDate) -> String {
+    match granularity {
+        StatsGranularity::Day => start.format("%Y-%m-%d").to_string(),
+        StatsGranularity::Week => start.format("%G-W%V").to_string(),
+        StatsGranularity::Month => start.format("%Y-%m").to_string(),
+    }
+}
+
 /// Get heatmap data for the past year
 #[tauri::command]
 pub fn get_heatmap_data(db: State<Database>, user_id: String) -> Result<Vec<HeatmapData>, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-    // Get data for the past 365 days
+    // Get data for the past 365 days of the user's calendar
+    let clock = UserClock::load(&conn, &user_id)?;
+    let since = (clock.today() - chrono::Duration::days(365))
+        .format("%Y-%m-%d")
+        .to_string();
+
     let mut stmt = conn
         .prepare(
             "SELECT date, total_focus_seconds / 60 as minutes 
              FROM daily_stats 
-             WHERE user_id = ?1 AND date >= date('now', 'localtime', '-365 days') 
+             WHERE user_id = ?1 AND date >= ?2 
              ORDER BY date ASC",
         )
         .map_err(|e| e.to_string())?;
 
     let data = stmt
-        .query_map(params![user_id], |row| {
+        .query_map(params![user_id, since], |row| {
             Ok(HeatmapData {
                 date: row.get(0)?,
                 value: row.get(1)?,
@@ -203,17 +973,16 @@ pub fn get_user_stats(db: State<Database>, user_id: String) -> Result<UserStats,
         .map_err(|e| e.to_string())?;
 
     // Tasks completed
-    let tasks_completed: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM tasks WHERE user_id = ?1 AND completed = 1",
-            params![user_id],
-            |row| row.get(0),
-        )
-        .map_err(|e| e.to_string())?;
+    let tasks_completed = count_completed_tasks(&conn, &user_id)?;
 
-    // Current streak calculation
-    let current_streak = calculate_current_streak(&conn, &user_id)?;
-    let longest_streak = calculate_longest_streak(&conn, &user_id)?;
+    // Streaks (missed days may be covered by freeze tokens)
+    let clock = UserClock::load(&conn, &user_id)?;
+    let active = active_dates(&conn, &user_id)?;
+    let frozen = frozen_dates(&conn, &user_id)?;
+    let current_streak = current_streak(&active, &frozen, clock.today());
+    let longest_streak = longest_streak(&active, &frozen);
+    let streak_freezes_available = available_freezes(&conn, &user_id)?;
+    let (goal_current_streak, goal_longest_streak) = goal_streaks(&conn, &user_id, clock.today())?;
 
     Ok(UserStats {
         total_focus_time,
@@ -221,98 +990,12 @@ pub fn get_user_stats(db: State<Database>, user_id: String) -> Result<UserStats,
         current_streak,
         longest_streak,
         tasks_completed,
+        streak_freezes_available,
+        frozen_dates: frozen
+            .iter()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .collect(),
+        goal_current_streak,
+        goal_longest_streak,
     })
 }
-
-/// Calculate current streak (consecutive days with sessions)
-fn calculate_current_streak(conn: &rusqlite::Connection, user_id: &str) -> Result<i64, String> {
-    let dates: Vec<String> = {
-        let mut stmt = conn
-            .prepare(
-                "SELECT DISTINCT date FROM daily_stats 
-                 WHERE user_id = ?1 AND total_focus_seconds > 0 
-                 ORDER BY date DESC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let result = stmt.query_map(params![user_id], |row| row.get(0))
-            .map_err(|e| e.to_string())?
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_string())?;
-        result
-    };
-
-    if dates.is_empty() {
-        return Ok(0);
-    }
-
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let yesterday = (chrono::Utc::now() - chrono::Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
-
-    // Streak must include today or yesterday
-    if dates[0] != today && dates[0] != yesterday {
-        return Ok(0);
-    }
-
-    let mut streak = 1i64;
-    for i in 1..dates.len() {
-        let prev_date =
-            chrono::NaiveDate::parse_from_str(&dates[i - 1], "%Y-%m-%d").map_err(|e| e.to_string())?;
-        let curr_date =
-            chrono::NaiveDate::parse_from_str(&dates[i], "%Y-%m-%d").map_err(|e| e.to_string())?;
-
-        if prev_date - curr_date == chrono::Duration::days(1) {
-            streak += 1;
-        } else {
-            break;
-        }
-    }
-
-    Ok(streak)
-}
-
-/// Calculate longest streak
-fn calculate_longest_streak(conn: &rusqlite::Connection, user_id: &str) -> Result<i64, String> {
-    let dates: Vec<String> = {
-        let mut stmt = conn
-            .prepare(
-                "SELECT DISTINCT date FROM daily_stats 
-                 WHERE user_id = ?1 AND total_focus_seconds > 0 
-                 ORDER BY date ASC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let result = stmt.query_map(params![user_id], |row| row.get(0))
-            .map_err(|e| e.to_string())?
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_string())?;
-        result
-    };
-
-    if dates.is_empty() {
-        return Ok(0);
-    }
-
-    let mut longest = 1i64;
-    let mut current = 1i64;
-
-    for i in 1..dates.len() {
-        let prev_date =
-            chrono::NaiveDate::parse_from_str(&dates[i - 1], "%Y-%m-%d").map_err(|e| e.to_string())?;
-        let curr_date =
-            chrono::NaiveDate::parse_from_str(&dates[i], "%Y-%m-%d").map_err(|e| e.to_string())?;
-
-        if curr_date - prev_date == chrono::Duration::days(1) {
-            current += 1;
-            if current > longest {
-                longest = current;
-            }
-        } else {
-            current = 1;
-        }
-    }
-
-    Ok(longest)
-}
@@ -1,3 +1,9 @@
+use crate::commands::clock::{
+    get_user_setting, parse_day_start_hour, parse_time_zone, DAY_START_HOUR_KEY, TIME_ZONE_KEY,
+};
+use crate::commands::reminder::{parse_reminder_lead_minutes, reminder_lead_minutes, REMINDER_LEAD_MINUTES_KEY};
+use crate::commands::session::rebuild_daily_stats_internal;
+use crate::commands::streak::resettle_streak_freezes;
 use crate::db::Database;
 use rusqlite::params;
 use std::collections::HashMap;
@@ -33,16 +39,19 @@ pub fn set_setting(
     value: String,
 ) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
-    let id = Uuid::new_v4().to_string();
 
-    conn.execute(
-        "INSERT INTO user_settings (id, user_id, key, value) VALUES (?1, ?2, ?3, ?4)
-         ON CONFLICT(user_id, key) DO UPDATE SET value = ?4",
-        params![id, user_id, key, value],
-    )
-    .map_err(|e| e.to_string())?;
-
-    Ok(())
+    match key.as_str() {
+        TIME_ZONE_KEY => set_time_zone_internal(&conn, &user_id, Some(&value)),
+        DAY_START_HOUR_KEY => {
+            let hour = parse_day_start_hour(&value)?;
+            set_day_start_hour_internal(&conn, &user_id, Some(hour))
+        }
+        REMINDER_LEAD_MINUTES_KEY => {
+            let minutes = parse_reminder_lead_minutes(&value)?;
+            upsert_setting(&conn, &user_id, &key, &minutes.to_string())
+        }
+        _ => upsert_setting(&conn, &user_id, &key, &value),
+    }
 }
 
 /// Get all settings for a user
@@ -77,6 +86,12 @@ pub fn delete_setting(
 ) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    match key.as_str() {
+        TIME_ZONE_KEY => return set_time_zone_internal(&conn, &user_id, None),
+        DAY_START_HOUR_KEY => return set_day_start_hour_internal(&conn, &user_id, None),
+        _ => {}
+    }
+
     conn.execute(
         "DELETE FROM user_settings WHERE user_id = ?1 AND key = ?2",
         params![user_id, key],
@@ -85,3 +100,142 @@ pub fn delete_setting(
 
     Ok(())
 }
+
+/// Get the user's configured time zone (None means the system zone is used)
+#[tauri::command]
+pub fn get_time_zone(db: State<Database>, user_id: String) -> Result<Option<String>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    get_user_setting(&conn, &user_id, TIME_ZONE_KEY)
+}
+
+/// Set (or clear) the user's time zone and re-bucket daily stats into the new days
+#[tauri::command]
+pub fn set_time_zone(
+    db: State<Database>,
+    user_id: String,
+    time_zone: Option<String>,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    set_time_zone_internal(&conn, &user_id, time_zone.as_deref())
+}
+
+fn set_time_zone_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    time_zone: Option<&str>,
+) -> Result<(), String> {
+    match time_zone {
+        Some(name) => {
+            let zone = parse_time_zone(name)?;
+            upsert_setting(conn, user_id, TIME_ZONE_KEY, zone.name())?;
+        }
+        None => {
+            conn.execute(
+                "DELETE FROM user_settings WHERE user_id = ?1 AND key = ?2",
+                params![user_id, TIME_ZONE_KEY],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    rebuild_daily_stats_internal(conn, user_id, None, None)?;
+    resettle_streak_freezes(conn, user_id)
+}
+
+/// Get the hour (0-23) at which the user's day starts
+#[tauri::command]
+pub fn get_day_start_hour(db: State<Database>, user_id: String) -> Result<u32, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let value = get_user_setting(&conn, &user_id, DAY_START_HOUR_KEY)?;
+
+    Ok(value
+        .and_then(|value| parse_day_start_hour(&value).ok())
+        .unwrap_or(0))
+}
+
+/// Set (or reset to midnight) the hour at which the user's day starts and
+/// re-bucket daily stats into the new days
+#[tauri::command]
+pub fn set_day_start_hour(
+    db: State<Database>,
+    user_id: String,
+    hour: Option<u32>,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    set_day_start_hour_internal(&conn, &user_id, hour)
+}
+
+fn set_day_start_hour_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    hour: Option<u32>,
+) -> Result<(), String> {
+    match hour {
+        Some(hour) => {
+            if hour >= 24 {
+                return Err(format!("Day start hour must be between 0 and 23: {}", hour));
+            }
+            upsert_setting(conn, user_id, DAY_START_HOUR_KEY, &hour.to_string())?;
+        }
+        None => {
+            conn.execute(
+                "DELETE FROM user_settings WHERE user_id = ?1 AND key = ?2",
+                params![user_id, DAY_START_HOUR_KEY],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    rebuild_daily_stats_internal(conn, user_id, None, None)?;
+    resettle_streak_freezes(conn, user_id)
+}
+
+/// Get how many minutes before a Reminder/Event task starts its reminder fires
+#[tauri::command]
+pub fn get_reminder_lead_minutes(db: State<Database>, user_id: String) -> Result<u32, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    reminder_lead_minutes(&conn, &user_id)
+}
+
+/// Set (or reset to the default) the reminder lead time in minutes
+#[tauri::command]
+pub fn set_reminder_lead_minutes(
+    db: State<Database>,
+    user_id: String,
+    minutes: Option<u32>,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    match minutes {
+        Some(minutes) => {
+            let minutes = parse_reminder_lead_minutes(&minutes.to_string())?;
+            upsert_setting(&conn, &user_id, REMINDER_LEAD_MINUTES_KEY, &minutes.to_string())
+        }
+        None => {
+            conn.execute(
+                "DELETE FROM user_settings WHERE user_id = ?1 AND key = ?2",
+                params![user_id, REMINDER_LEAD_MINUTES_KEY],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+}
+
+fn upsert_setting(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), String> {
+    let id = Uuid::new_v4().to_string();
+
+    conn.execute(
+        "INSERT INTO user_settings (id, user_id, key, value) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(user_id, key) DO UPDATE SET value = ?4",
+        params![id, user_id, key, value],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
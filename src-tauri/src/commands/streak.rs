@@ -0,0 +1,328 @@
+use crate::commands::clock::UserClock;
+use crate::db::Database;
+use crate::models::StreakFreeze;
+use chrono::NaiveDate;
+use rusqlite::params;
+use std::collections::BTreeSet;
+use tauri::State;
+use uuid::Uuid;
+
+/// Consecutive focused days needed to earn a freeze token
+const DAYS_PER_EARNED_FREEZE: i64 = 7;
+
+/// Unused tokens a user can hold before earning stops
+const MAX_BANKED_FREEZES: i64 = 2;
+
+/// Tokens a single grant may add
+const MAX_GRANTED_FREEZES: i64 = 30;
+
+/// Get all streak freeze tokens for a user
+#[tauri::command]
+pub fn get_streak_freezes(db: State<Database>, user_id: String) -> Result<Vec<StreakFreeze>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    load_streak_freezes(&conn, &user_id)
+}
+
+/// Grant freeze tokens to a user, returning how many are now available
+#[tauri::command]
+pub fn grant_streak_freezes(db: State<Database>, user_id: String, count: i64) -> Result<i64, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    if count <= 0 || count > MAX_GRANTED_FREEZES {
+        return Err(format!("Freeze count must be between 1 and {}", MAX_GRANTED_FREEZES));
+    }
+
+    for _ in 0..count {
+        insert_streak_freeze(&conn, &user_id, "granted", None)?;
+    }
+
+    available_freezes(&conn, &user_id)
+}
+
+/// Load all streak freeze tokens for a user
+pub(crate) fn load_streak_freezes(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<StreakFreeze>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, source, earned_for, granted_at, used_on
+             FROM streak_freezes
+             WHERE user_id = ?1
+             ORDER BY granted_at ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let freezes = stmt
+        .query_map(params![user_id], |row| {
+            Ok(StreakFreeze {
+                id: row.get(0)?,
+                source: row.get(1)?,
+                earned_for: row.get(2)?,
+                granted_at: row.get(3)?,
+                used_on: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(freezes)
+}
+
+/// Number of unused freeze tokens
+pub(crate) fn available_freezes(conn: &rusqlite::Connection, user_id: &str) -> Result<i64, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM streak_freezes WHERE user_id = ?1 AND used_on IS NULL",
+        params![user_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Days with any focus time
+pub(crate) fn active_dates(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<BTreeSet<NaiveDate>, String> {
+    let dates: Vec<String> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT date FROM daily_stats
+                 WHERE user_id = ?1 AND total_focus_seconds > 0",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![user_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    parse_dates(&dates)
+}
+
+/// Days covered by a used freeze token
+pub(crate) fn frozen_dates(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<BTreeSet<NaiveDate>, String> {
+    let dates: Vec<String> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT used_on FROM streak_freezes
+                 WHERE user_id = ?1 AND used_on IS NOT NULL",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![user_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    parse_dates(&dates)
+}
+
+/// Consecutive qualifying days ending today (or yesterday, since today is
+/// still in progress). Frozen days keep a streak alive without adding to it.
+pub(crate) fn current_streak(
+    qualifying: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> i64 {
+    current_streak_days(qualifying, frozen, today).len() as i64
+}
+
+/// Qualifying days of the current streak, oldest first
+fn current_streak_days(
+    qualifying: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> Vec<NaiveDate> {
+    let covered = |date: &NaiveDate| qualifying.contains(date) || frozen.contains(date);
+
+    let mut cursor = today;
+    if !covered(&cursor) {
+        cursor = today - chrono::Duration::days(1);
+    }
+
+    let mut days = Vec::new();
+    while covered(&cursor) {
+        if qualifying.contains(&cursor) {
+            days.push(cursor);
+        }
+        cursor -= chrono::Duration::days(1);
+    }
+
+    days.reverse();
+    days
+}
+
+/// Longest run of qualifying days, bridging frozen days
+pub(crate) fn longest_streak(qualifying: &BTreeSet<NaiveDate>, frozen: &BTreeSet<NaiveDate>) -> i64 {
+    let covered: BTreeSet<&NaiveDate> = qualifying.iter().chain(frozen.iter()).collect();
+
+    let mut longest = 0i64;
+    let mut current = 0i64;
+    let mut previous: Option<NaiveDate> = None;
+
+    for date in covered {
+        let consecutive = previous.is_some_and(|prev| *date - prev == chrono::Duration::days(1));
+        if !consecutive {
+            current = 0;
+        }
+        if qualifying.contains(date) {
+            current += 1;
+        }
+        longest = longest.max(current);
+        previous = Some(*date);
+    }
+
+    longest
+}
+
+/// Settle streak freezes for every user, covering days missed since the app
+/// last ran
+pub(crate) fn apply_all_streak_freezes(conn: &rusqlite::Connection) -> Result<(), String> {
+    let user_ids: Vec<String> = {
+        let mut stmt = conn
+            .prepare("SELECT id FROM users")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    for user_id in user_ids {
+        apply_streak_freezes(conn, &user_id)?;
+    }
+
+    Ok(())
+}
+
+/// Spend tokens on days missed since the last focused day (if there are
+/// enough to cover all of them), then earn tokens for streak milestones.
+pub(crate) fn apply_streak_freezes(conn: &rusqlite::Connection, user_id: &str) -> Result<(), String> {
+    let clock = UserClock::load(conn, user_id)?;
+    let today = clock.today();
+    let active = active_dates(conn, user_id)?;
+    let frozen = frozen_dates(conn, user_id)?;
+
+    let last_covered = active.iter().chain(frozen.iter()).filter(|date| **date < today).max();
+
+    if let Some(last_covered) = last_covered {
+        let missed: Vec<NaiveDate> = last_covered
+            .succ_opt()
+            .into_iter()
+            .flat_map(|first| first.iter_days().take_while(|date| *date < today))
+            .collect();
+
+        if !missed.is_empty() && missed.len() as i64 <= available_freezes(conn, user_id)? {
+            for date in &missed {
+                use_streak_freeze(conn, user_id, *date)?;
+            }
+        }
+    }
+
+    earn_streak_freezes(conn, user_id, today)
+}
+
+/// Settle freezes again after daily stats were re-bucketed: tokens spent on
+/// days that now have focus time are refunded before gaps are covered anew
+pub(crate) fn resettle_streak_freezes(conn: &rusqlite::Connection, user_id: &str) -> Result<(), String> {
+    conn.execute(
+        "UPDATE streak_freezes SET used_on = NULL
+         WHERE user_id = ?1 AND used_on IN (
+            SELECT date FROM daily_stats
+            WHERE user_id = ?1 AND total_focus_seconds > 0
+         )",
+        params![user_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    apply_streak_freezes(conn, user_id)
+}
+
+/// Grant one token per milestone of the current streak, up to the banking limit
+fn earn_streak_freezes(conn: &rusqlite::Connection, user_id: &str, today: NaiveDate) -> Result<(), String> {
+    let active = active_dates(conn, user_id)?;
+    let frozen = frozen_dates(conn, user_id)?;
+
+    // Each milestone is identified by the day it was reached
+    let streak_days = current_streak_days(&active, &frozen, today);
+    for (index, date) in streak_days.iter().enumerate() {
+        let reached = index as i64 + 1;
+        if reached % DAYS_PER_EARNED_FREEZE != 0 {
+            continue;
+        }
+        if available_freezes(conn, user_id)? >= MAX_BANKED_FREEZES {
+            break;
+        }
+
+        let earned_for = date.format("%Y-%m-%d").to_string();
+        let already_earned: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM streak_freezes WHERE user_id = ?1 AND earned_for = ?2)",
+                params![user_id, earned_for],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        if !already_earned {
+            insert_streak_freeze(conn, user_id, "earned", Some(&earned_for))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn use_streak_freeze(conn: &rusqlite::Connection, user_id: &str, date: NaiveDate) -> Result<(), String> {
+    conn.execute(
+        "UPDATE streak_freezes SET used_on = ?1
+         WHERE id = (
+            SELECT id FROM streak_freezes
+            WHERE user_id = ?2 AND used_on IS NULL
+            ORDER BY granted_at ASC
+            LIMIT 1
+         )",
+        params![date.format("%Y-%m-%d").to_string(), user_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn insert_streak_freeze(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    source: &str,
+    earned_for: Option<&str>,
+) -> Result<(), String> {
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO streak_freezes (id, user_id, source, earned_for, granted_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, user_id, source, earned_for, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn parse_dates(dates: &[String]) -> Result<BTreeSet<NaiveDate>, String> {
+    dates
+        .iter()
+        .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| e.to_string()))
+        .collect()
+}
@@ -0,0 +1,284 @@
+use crate::db::Database;
+use crate::models::{CreateSubjectRequest, Subject, UpdateSubjectRequest};
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+use uuid::Uuid;
+
+/// Create a new subject
+#[tauri::command]
+pub fn create_subject(db: State<Database>, request: CreateSubjectRequest) -> Result<Subject, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let name = normalize_subject_name(&request.name)?;
+    if find_subject_by_name(&conn, &request.user_id, &name)?.is_some() {
+        return Err(format!("A subject named \"{}\" already exists", name));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO subjects (id, user_id, name, color, icon, archived, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
+        params![id, request.user_id, name, request.color, request.icon, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_subject_by_id(&conn, &id)
+}
+
+/// Get a user's subjects, leaving out archived ones unless asked for
+#[tauri::command]
+pub fn get_subjects(
+    db: State<Database>,
+    user_id: String,
+    include_archived: Option<bool>,
+) -> Result<Vec<Subject>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let subjects = load_subjects(&conn, &user_id)?
+        .into_iter()
+        .filter(|subject| include_archived.unwrap_or(false) || !subject.archived)
+        .collect();
+
+    Ok(subjects)
+}
+
+/// Update a subject; renaming it renames the category of its sessions
+#[tauri::command]
+pub fn update_subject(
+    db: State<Database>,
+    subject_id: String,
+    request: UpdateSubjectRequest,
+) -> Result<Subject, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let existing = get_subject_by_id(&conn, &subject_id)?;
+
+    let name = match request.name {
+        Some(ref name) => {
+            let name = normalize_subject_name(name)?;
+            let clash = find_subject_by_name(&conn, &existing.user_id, &name)?;
+            if clash.is_some_and(|other| other.id != subject_id) {
+                return Err(format!("A subject named \"{}\" already exists", name));
+            }
+            Some(name)
+        }
+        None => None,
+    };
+
+    // Build dynamic update query
+    let mut updates = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(ref name) = name {
+        params_vec.push(Box::new(name.clone()));
+        updates.push(format!("name = ?{}", params_vec.len()));
+    }
+    if let Some(ref color) = request.color {
+        params_vec.push(Box::new(color.clone()));
+        updates.push(format!("color = ?{}", params_vec.len()));
+    }
+    if let Some(ref icon) = request.icon {
+        params_vec.push(Box::new(icon.clone()));
+        updates.push(format!("icon = ?{}", params_vec.len()));
+    }
+    if let Some(archived) = request.archived {
+        params_vec.push(Box::new(if archived { 1 } else { 0 }));
+        updates.push(format!("archived = ?{}", params_vec.len()));
+    }
+
+    if !updates.is_empty() {
+        updates.push("updated_at = datetime('now')".to_string());
+        params_vec.push(Box::new(subject_id.clone()));
+        let query = format!(
+            "UPDATE subjects SET {} WHERE id = ?{}",
+            updates.join(", "),
+            params_vec.len()
+        );
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+        conn.execute(&query, params_refs.as_slice())
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(ref name) = name {
+        rename_subject_references(&conn, &existing, name)?;
+    }
+
+    get_subject_by_id(&conn, &subject_id)
+}
+
+/// Delete a subject; its sessions become uncategorized
+#[tauri::command]
+pub fn delete_subject(db: State<Database>, subject_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE focus_sessions SET category = NULL WHERE subject_id = ?1",
+        params![subject_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM subjects WHERE id = ?1", params![subject_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Move every session of `source_id` onto `target_id` and delete the source
+/// (e.g. fold "Maths" into "Math")
+#[tauri::command]
+pub fn merge_subjects(
+    db: State<Database>,
+    source_id: String,
+    target_id: String,
+) -> Result<Subject, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let source = get_subject_by_id(&conn, &source_id)?;
+    let target = get_subject_by_id(&conn, &target_id)?;
+    if source.id == target.id {
+        return Err("Cannot merge a subject into itself".to_string());
+    }
+    if source.user_id != target.user_id {
+        return Err("Subjects belong to different users".to_string());
+    }
+
+    conn.execute(
+        "UPDATE focus_sessions SET subject_id = ?1, category = ?2 WHERE subject_id = ?3",
+        params![target.id, target.name, source.id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    rename_subject_references(&conn, &source, &target.name)?;
+
+    conn.execute("DELETE FROM subjects WHERE id = ?1", params![source.id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(target)
+}
+
+/// Resolve the subject a session belongs to, returning its id and name.
+/// An explicit subject id wins; otherwise the category is matched by name
+/// (ignoring case and surrounding whitespace) and created if it is new.
+pub(crate) fn resolve_session_subject(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    subject_id: Option<&str>,
+    category: Option<&str>,
+) -> Result<(Option<String>, Option<String>), String> {
+    if let Some(subject_id) = subject_id {
+        let subject = get_subject_by_id(conn, subject_id)?;
+        if subject.user_id != user_id {
+            return Err("Subject not found".to_string());
+        }
+        return Ok((Some(subject.id), Some(subject.name)));
+    }
+
+    let name = match category.map(str::trim) {
+        Some(name) if !name.is_empty() => name,
+        _ => return Ok((None, None)),
+    };
+
+    if let Some(subject) = find_subject_by_name(conn, user_id, name)? {
+        return Ok((Some(subject.id), Some(subject.name)));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO subjects (id, user_id, name) VALUES (?1, ?2, ?3)",
+        params![id, user_id, name],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok((Some(id), Some(name.to_string())))
+}
+
+/// Load all of a user's subjects, archived ones included
+pub(crate) fn load_subjects(conn: &rusqlite::Connection, user_id: &str) -> Result<Vec<Subject>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, user_id, name, color, icon, archived, created_at
+             FROM subjects
+             WHERE user_id = ?1
+             ORDER BY name COLLATE NOCASE ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let subjects = stmt
+        .query_map(params![user_id], map_subject)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(subjects)
+}
+
+fn get_subject_by_id(conn: &rusqlite::Connection, subject_id: &str) -> Result<Subject, String> {
+    conn.query_row(
+        "SELECT id, user_id, name, color, icon, archived, created_at FROM subjects WHERE id = ?1",
+        params![subject_id],
+        map_subject,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Subject not found".to_string())
+}
+
+pub(crate) fn find_subject_by_name(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    name: &str,
+) -> Result<Option<Subject>, String> {
+    conn.query_row(
+        "SELECT id, user_id, name, color, icon, archived, created_at
+         FROM subjects
+         WHERE user_id = ?1 AND name = ?2 COLLATE NOCASE",
+        params![user_id, name.trim()],
+        map_subject,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn map_subject(row: &rusqlite::Row) -> rusqlite::Result<Subject> {
+    Ok(Subject {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        name: row.get(2)?,
+        color: row.get(3)?,
+        icon: row.get(4)?,
+        archived: row.get::<_, i32>(5)? == 1,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Point category text that referred to a subject at its new name
+fn rename_subject_references(
+    conn: &rusqlite::Connection,
+    subject: &Subject,
+    new_name: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "UPDATE focus_sessions SET category = ?1 WHERE subject_id = ?2",
+        params![new_name, subject.id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE period_goals SET category = ?1 WHERE user_id = ?2 AND category = ?3 COLLATE NOCASE",
+        params![new_name, subject.user_id, subject.name],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn normalize_subject_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Subject name cannot be empty".to_string());
+    }
+    Ok(name.to_string())
+}
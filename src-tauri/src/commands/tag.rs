@@ -0,0 +1,181 @@
+use crate::db::Database;
+use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+use uuid::Uuid;
+
+/// Create a new tag
+#[tauri::command]
+pub fn create_tag(db: State<Database>, request: CreateTagRequest) -> Result<Tag, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let name = normalize_tag_name(&request.name)?;
+    if find_tag_by_name(&conn, &request.user_id, &name)?.is_some() {
+        return Err(format!("A tag named \"{}\" already exists", name));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO tags (id, user_id, name, color, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+        params![id, request.user_id, name, request.color, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_tag_by_id(&conn, &id)
+}
+
+/// Get a user's tags
+#[tauri::command]
+pub fn get_tags(db: State<Database>, user_id: String) -> Result<Vec<Tag>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    load_tags(&conn, &user_id)
+}
+
+/// Rename or recolor a tag
+#[tauri::command]
+pub fn update_tag(db: State<Database>, tag_id: String, request: UpdateTagRequest) -> Result<Tag, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let existing = get_tag_by_id(&conn, &tag_id)?;
+
+    if let Some(ref name) = request.name {
+        let name = normalize_tag_name(name)?;
+        let clash = find_tag_by_name(&conn, &existing.user_id, &name)?;
+        if clash.is_some_and(|other| other.id != tag_id) {
+            return Err(format!("A tag named \"{}\" already exists", name));
+        }
+
+        conn.execute(
+            "UPDATE tags SET name = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![name, tag_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(ref color) = request.color {
+        conn.execute(
+            "UPDATE tags SET color = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![color, tag_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    get_tag_by_id(&conn, &tag_id)
+}
+
+/// Delete a tag, removing it from every task
+#[tauri::command]
+pub fn delete_tag(db: State<Database>, tag_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM tags WHERE id = ?1", params![tag_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Replace a task's tags with the named ones, creating tags that do not
+/// exist yet (names match ignoring case and surrounding whitespace)
+pub(crate) fn set_task_tags(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    task_id: &str,
+    names: &[String],
+) -> Result<(), String> {
+    let mut tag_ids = Vec::new();
+    for name in names {
+        let name = normalize_tag_name(name)?;
+        let tag_id = match find_tag_by_name(conn, user_id, &name)? {
+            Some(tag) => tag.id,
+            None => {
+                let id = Uuid::new_v4().to_string();
+                conn.execute(
+                    "INSERT INTO tags (id, user_id, name) VALUES (?1, ?2, ?3)",
+                    params![id, user_id, name],
+                )
+                .map_err(|e| e.to_string())?;
+                id
+            }
+        };
+        if !tag_ids.contains(&tag_id) {
+            tag_ids.push(tag_id);
+        }
+    }
+
+    conn.execute("DELETE FROM task_tags WHERE task_id = ?1", params![task_id])
+        .map_err(|e| e.to_string())?;
+
+    for tag_id in tag_ids {
+        conn.execute(
+            "INSERT INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+            params![task_id, tag_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Load all of a user's tags
+pub(crate) fn load_tags(conn: &rusqlite::Connection, user_id: &str) -> Result<Vec<Tag>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, user_id, name, color, created_at
+             FROM tags
+             WHERE user_id = ?1
+             ORDER BY name COLLATE NOCASE ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let tags = stmt
+        .query_map(params![user_id], map_tag)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(tags)
+}
+
+fn get_tag_by_id(conn: &rusqlite::Connection, tag_id: &str) -> Result<Tag, String> {
+    conn.query_row(
+        "SELECT id, user_id, name, color, created_at FROM tags WHERE id = ?1",
+        params![tag_id],
+        map_tag,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Tag not found".to_string())
+}
+
+fn find_tag_by_name(conn: &rusqlite::Connection, user_id: &str, name: &str) -> Result<Option<Tag>, String> {
+    conn.query_row(
+        "SELECT id, user_id, name, color, created_at
+         FROM tags
+         WHERE user_id = ?1 AND name = ?2 COLLATE NOCASE",
+        params![user_id, name.trim()],
+        map_tag,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn map_tag(row: &rusqlite::Row) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        name: row.get(2)?,
+        color: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+fn normalize_tag_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Tag name cannot be empty".to_string());
+    }
+    Ok(name.to_string())
+}
@@ -1,6 +1,17 @@
+use crate::commands::recurrence::RecurrenceRule;
+use crate::commands::rollover::record_task_date_change;
+use crate::commands::tag::set_task_tags;
+use crate::commands::task_history::record_task_event;
+use crate::commands::task_item::{set_task_items_completed, sync_task_completion};
 use crate::db::Database;
-use crate::models::{CreateTaskRequest, Task, TaskCategory, UpdateTaskRequest};
-use rusqlite::params;
+use crate::models::{
+    CategoryEstimate, CreateTaskRequest, DateChangeReason, EstimateReport, SortDirection, Task,
+    TaskCategory, TaskEstimate, TaskEventType, TaskOccurrence, TaskPage, TaskPriority, TaskQuery,
+    TaskSortField, UpdateTaskRequest,
+};
+use chrono::NaiveDate;
+use rusqlite::{params, OptionalExtension};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use tauri::State;
 use uuid::Uuid;
 
@@ -9,12 +20,25 @@ use uuid::Uuid;
 pub fn create_task(db: State<Database>, request: CreateTaskRequest) -> Result<Task, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    insert_task(&conn, &request)
+}
+
+/// Insert a task and log its creation
+pub(crate) fn insert_task(conn: &rusqlite::Connection, request: &CreateTaskRequest) -> Result<Task, String> {
+    validate_estimate(request.estimated_minutes)?;
+    let recurrence = normalize_recurrence(request.recurrence.as_deref())?;
+    if recurrence.is_some() {
+        parse_task_date(&request.date)?;
+    }
+    let auto_complete = request.auto_complete.unwrap_or(false);
+    validate_auto_complete(auto_complete, recurrence.as_deref())?;
+
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     conn.execute(
-        "INSERT INTO tasks (id, user_id, title, category, date, start_time, end_time, completed, created_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8)",
+        "INSERT INTO tasks (id, user_id, title, category, date, start_time, end_time, completed, created_at, estimated_minutes, recurrence, auto_complete, priority) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8, ?9, ?10, ?11, ?12)",
         params![
             id,
             request.user_id,
@@ -23,96 +47,132 @@ pub fn create_task(db: State<Database>, request: CreateTaskRequest) -> Result<Ta
             request.date,
             request.start_time,
             request.end_time,
-            now
+            now,
+            request.estimated_minutes,
+            recurrence,
+            if auto_complete { 1 } else { 0 },
+            request.priority.unwrap_or_default().as_str()
         ],
     )
     .map_err(|e| e.to_string())?;
 
-    let task = Task {
-        id,
-        user_id: request.user_id,
-        title: request.title,
-        category: request.category,
-        date: request.date,
-        start_time: request.start_time,
-        end_time: request.end_time,
-        completed: false,
-        created_at: now,
-    };
+    if let Some(ref tags) = request.tags {
+        set_task_tags(conn, &request.user_id, &id, tags)?;
+    }
+
+    let task = get_task_by_id(conn, &id)?;
+    record_task_event(conn, &task, None, TaskEventType::Created)?;
 
     Ok(task)
 }
 
-/// Get tasks for a user, optionally filtered by date
+/// Get tasks for a user, optionally filtered by a date or a date range
+/// (`date` to `end_date`). When filtering, recurring tasks are expanded into
+/// their occurrences; without a filter each series is returned once.
 #[tauri::command]
 pub fn get_tasks(
     db: State<Database>,
     user_id: String,
     date: Option<String>,
+    end_date: Option<String>,
 ) -> Result<Vec<Task>, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let tasks: Vec<Task> = if let Some(ref filter_date) = date {
-        let mut stmt = conn
-            .prepare(
-                "SELECT id, user_id, title, category, date, start_time, end_time, completed, created_at 
-                 FROM tasks 
-                 WHERE user_id = ?1 AND date = ?2 
-                 ORDER BY start_time ASC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let rows = stmt.query_map(params![user_id, filter_date], |row| {
-            let category_str: String = row.get(3)?;
-            Ok(Task {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                title: row.get(2)?,
-                category: TaskCategory::from_str(&category_str).unwrap_or(TaskCategory::ToDo),
-                date: row.get(4)?,
-                start_time: row.get(5)?,
-                end_time: row.get(6)?,
-                completed: row.get::<_, i32>(7)? == 1,
-                created_at: row.get(8)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-        
-        rows.collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_string())?
+        let start = parse_task_date(filter_date)?;
+        let end = match end_date {
+            Some(ref end_date) => parse_task_date(end_date)?,
+            None => start,
+        };
+        if end < start {
+            return Err("End date must not be before start date".to_string());
+        }
+
+        tasks_in_range(&conn, &user_id, start, end)?
     } else {
-        let mut stmt = conn
-            .prepare(
-                "SELECT id, user_id, title, category, date, start_time, end_time, completed, created_at 
-                 FROM tasks 
-                 WHERE user_id = ?1 
-                 ORDER BY date DESC, start_time ASC",
-            )
-            .map_err(|e| e.to_string())?;
-
-        let rows = stmt.query_map(params![user_id], |row| {
-            let category_str: String = row.get(3)?;
-            Ok(Task {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                title: row.get(2)?,
-                category: TaskCategory::from_str(&category_str).unwrap_or(TaskCategory::ToDo),
-                date: row.get(4)?,
-                start_time: row.get(5)?,
-                end_time: row.get(6)?,
-                completed: row.get::<_, i32>(7)? == 1,
-                created_at: row.get(8)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-        
-        rows.collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_string())?
+        load_user_tasks(&conn, &user_id)?
     };
 
     Ok(tasks)
 }
 
+/// Query tasks with optional date range, completion, category, priority,
+/// tag and title filters, sorted and paged. Within a date range recurring tasks are
+/// expanded into occurrences, so a calendar can load a whole month at once.
+#[tauri::command]
+pub fn query_tasks(db: State<Database>, query: TaskQuery) -> Result<TaskPage, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut tasks = match (&query.start_date, &query.end_date) {
+        (Some(start_date), end_date) => {
+            let start = parse_task_date(start_date)?;
+            let end = match end_date {
+                Some(end_date) => parse_task_date(end_date)?,
+                None => start,
+            };
+            if end < start {
+                return Err("End date must not be before start date".to_string());
+            }
+            tasks_in_range(&conn, &query.user_id, start, end)?
+        }
+        (None, Some(_)) => return Err("An end date needs a start date".to_string()),
+        (None, None) => load_user_tasks(&conn, &query.user_id)?,
+    };
+
+    let search = query
+        .search
+        .as_deref()
+        .map(|search| search.trim().to_lowercase())
+        .filter(|search| !search.is_empty());
+    let tags: Option<Vec<String>> = query
+        .tags
+        .as_ref()
+        .map(|tags| tags.iter().map(|tag| tag.trim().to_lowercase()).collect());
+
+    tasks.retain(|task| {
+        query.completed.map_or(true, |completed| task.completed == completed)
+            && query
+                .categories
+                .as_ref()
+                .map_or(true, |categories| categories.contains(&task.category))
+            && query
+                .priorities
+                .as_ref()
+                .map_or(true, |priorities| priorities.contains(&task.priority))
+            && tags.as_ref().map_or(true, |tags| {
+                task.tags.iter().any(|tag| tags.contains(&tag.to_lowercase()))
+            })
+            && search
+                .as_ref()
+                .map_or(true, |search| task.title.to_lowercase().contains(search))
+    });
+
+    let sort_by = query.sort_by.unwrap_or(TaskSortField::Date);
+    tasks.sort_by(|a, b| {
+        let by_date = (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time));
+        let ordering = match sort_by {
+            TaskSortField::Date => by_date,
+            TaskSortField::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()).then(by_date),
+            TaskSortField::Category => a.category.as_str().cmp(b.category.as_str()).then(by_date),
+            TaskSortField::CreatedAt => a.created_at.cmp(&b.created_at).then(by_date),
+            TaskSortField::Priority => a.priority.rank().cmp(&b.priority.rank()).then(by_date),
+        };
+        match query.sort_direction {
+            Some(SortDirection::Desc) => ordering.reverse(),
+            _ => ordering,
+        }
+    });
+
+    let total = tasks.len() as i64;
+    let offset = query.offset.unwrap_or(0) as usize;
+    let tasks = match query.limit {
+        Some(limit) => tasks.into_iter().skip(offset).take(limit as usize).collect(),
+        None => tasks.into_iter().skip(offset).collect(),
+    };
+
+    Ok(TaskPage { tasks, total })
+}
+
 /// Update a task
 #[tauri::command]
 pub fn update_task(
@@ -122,6 +182,35 @@ pub fn update_task(
 ) -> Result<Task, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    apply_task_update(&conn, &task_id, &request)
+}
+
+/// Apply an update to a task, keeping its date history, event log and
+/// checklist completion in step
+pub(crate) fn apply_task_update(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    request: &UpdateTaskRequest,
+) -> Result<Task, String> {
+    if let Some(estimated_minutes) = request.estimated_minutes {
+        if estimated_minutes != 0 {
+            validate_estimate(Some(estimated_minutes))?;
+        }
+    }
+    let recurrence = request
+        .recurrence
+        .as_deref()
+        .map(|rule| normalize_recurrence(Some(rule)))
+        .transpose()?;
+
+    let existing = get_task_by_id(conn, task_id)?;
+    let auto_complete = request.auto_complete.unwrap_or(existing.auto_complete);
+    let effective_recurrence = match recurrence {
+        Some(ref recurrence) => recurrence.clone(),
+        None => existing.recurrence.clone(),
+    };
+    validate_auto_complete(auto_complete, effective_recurrence.as_deref())?;
+
     // Build dynamic update query
     let mut updates = Vec::new();
     let mut param_idx = 1;
@@ -149,10 +238,35 @@ pub fn update_task(
     if request.completed.is_some() {
         updates.push(format!("completed = ?{}", param_idx));
         param_idx += 1;
+        updates.push(format!("completed_at = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.estimated_minutes.is_some() {
+        updates.push(format!("estimated_minutes = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if recurrence.is_some() {
+        updates.push(format!("recurrence = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.auto_complete.is_some() {
+        updates.push(format!("auto_complete = ?{}", param_idx));
+        param_idx += 1;
+    }
+    if request.priority.is_some() {
+        updates.push(format!("priority = ?{}", param_idx));
+        param_idx += 1;
+    }
+
+    if let Some(ref tags) = request.tags {
+        set_task_tags(conn, &existing.user_id, task_id, tags)?;
     }
 
     if updates.is_empty() {
-        return get_task_by_id(&conn, &task_id);
+        if request.tags.is_some() {
+            record_task_event(conn, &existing, None, TaskEventType::Updated)?;
+        }
+        return get_task_by_id(conn, task_id);
     }
 
     let query = format!(
@@ -181,15 +295,63 @@ pub fn update_task(
     }
     if let Some(completed) = request.completed {
         params_vec.push(Box::new(if completed { 1 } else { 0 }));
+        // Keep the original completion time when a completed task stays completed
+        let completed_at = match (completed, existing.completed) {
+            (true, true) => existing.completed_at.clone(),
+            (true, false) => Some(chrono::Utc::now().to_rfc3339()),
+            (false, _) => None,
+        };
+        params_vec.push(Box::new(completed_at));
+    }
+    if let Some(estimated_minutes) = request.estimated_minutes {
+        params_vec.push(Box::new(Some(estimated_minutes).filter(|minutes| *minutes != 0)));
+    }
+    if let Some(ref recurrence) = recurrence {
+        params_vec.push(Box::new(recurrence.clone()));
     }
-    params_vec.push(Box::new(task_id.clone()));
+    if let Some(auto_complete) = request.auto_complete {
+        params_vec.push(Box::new(if auto_complete { 1 } else { 0 }));
+    }
+    if let Some(priority) = request.priority {
+        params_vec.push(Box::new(priority.as_str()));
+    }
+    params_vec.push(Box::new(task_id.to_string()));
 
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
     conn.execute(&query, params_refs.as_slice())
         .map_err(|e| e.to_string())?;
 
-    get_task_by_id(&conn, &task_id)
+    // Keep a history of one-off tasks being moved to another day
+    if let Some(ref date) = request.date {
+        if *date != existing.date && existing.recurrence.is_none() && effective_recurrence.is_none() {
+            record_task_date_change(conn, task_id, &existing.date, date, DateChangeReason::Reschedule)?;
+        }
+    }
+
+    // Anything besides the completed/completed_at columns counts as an edit
+    let completion_columns = if request.completed.is_some() { 2 } else { 0 };
+    let edited = updates.len() > completion_columns || request.tags.is_some();
+    let updated = get_task_by_id(conn, task_id)?;
+    if edited {
+        record_task_event(conn, &updated, None, TaskEventType::Updated)?;
+    }
+    if let Some(completed) = request.completed {
+        if completed != existing.completed {
+            let event_type = if completed { TaskEventType::Completed } else { TaskEventType::Reopened };
+            record_task_event(conn, &updated, None, event_type)?;
+        }
+    }
+
+    // Completing or reopening a checklist-driven task does the same to its items
+    if auto_complete {
+        if let Some(completed) = request.completed {
+            set_task_items_completed(conn, task_id, completed)?;
+        }
+        sync_task_completion(conn, task_id)?;
+    }
+
+    get_task_by_id(conn, task_id)
 }
 
 /// Delete a task
@@ -197,58 +359,681 @@ pub fn update_task(
 pub fn delete_task(db: State<Database>, task_id: String) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    let task = conn
+        .query_row(
+            &format!("{} WHERE t.id = ?1", TASK_SELECT),
+            params![task_id],
+            map_task,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    // Deleting a task that is already gone has nothing to log
+    if let Some(ref task) = task {
+        record_task_event(&conn, task, None, TaskEventType::Deleted)?;
+    }
+
     conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])
         .map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-/// Toggle task completion status
+/// Toggle task completion status; occurrences of a recurring task are
+/// completed one at a time by passing the occurrence date
 #[tauri::command]
-pub fn toggle_task_completion(db: State<Database>, task_id: String) -> Result<Task, String> {
+pub fn toggle_task_completion(
+    db: State<Database>,
+    task_id: String,
+    occurrence_date: Option<String>,
+) -> Result<Task, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    if let Some(ref occurrence_date) = occurrence_date {
+        require_occurrence(&conn, &task_id, occurrence_date)?;
+        let occurrence = get_occurrence_task(&conn, &task_id, occurrence_date)?;
+        set_task_completed(&conn, &task_id, Some(occurrence_date), !occurrence.completed)?;
+
+        return get_occurrence_task(&conn, &task_id, occurrence_date);
+    }
+
     // Get current completion status
-    let current_completed: i32 = conn
+    let (current_completed, recurrence, auto_complete): (i32, Option<String>, i32) = conn
         .query_row(
-            "SELECT completed FROM tasks WHERE id = ?1",
+            "SELECT completed, recurrence, auto_complete FROM tasks WHERE id = ?1",
             params![task_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )
         .map_err(|e| e.to_string())?;
 
+    if recurrence.is_some() {
+        return Err("Recurring tasks are completed per occurrence".to_string());
+    }
+
     // Toggle the status
-    let new_completed = if current_completed == 1 { 0 } else { 1 };
+    let new_completed = current_completed != 1;
+    set_task_completed(&conn, &task_id, None, new_completed)?;
+
+    // Ticking off a checklist-driven task ticks off (or reopens) all its items
+    if auto_complete == 1 {
+        set_task_items_completed(&conn, &task_id, new_completed)?;
+    }
+
+    get_task_by_id(&conn, &task_id)
+}
+
+/// Skip one occurrence of a recurring task
+#[tauri::command]
+pub fn skip_task_occurrence(
+    db: State<Database>,
+    task_id: String,
+    occurrence_date: String,
+) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    skip_occurrence(&conn, &task_id, &occurrence_date)
+}
+
+/// Move one occurrence of a recurring task to another date and/or time
+#[tauri::command]
+pub fn move_task_occurrence(
+    db: State<Database>,
+    task_id: String,
+    occurrence_date: String,
+    date: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Task, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    move_occurrence(
+        &conn,
+        &task_id,
+        &occurrence_date,
+        &date,
+        start_time.as_deref(),
+        end_time.as_deref(),
+    )
+}
+
+/// Undo completion, skipping or moving of one occurrence
+#[tauri::command]
+pub fn restore_task_occurrence(
+    db: State<Database>,
+    task_id: String,
+    occurrence_date: String,
+) -> Result<Task, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
+    require_occurrence(&conn, &task_id, &occurrence_date)?;
     conn.execute(
-        "UPDATE tasks SET completed = ?1 WHERE id = ?2",
-        params![new_completed, task_id],
+        "DELETE FROM task_occurrences WHERE task_id = ?1 AND occurrence_date = ?2",
+        params![task_id, occurrence_date],
     )
     .map_err(|e| e.to_string())?;
 
-    get_task_by_id(&conn, &task_id)
+    get_occurrence_task(&conn, &task_id, &occurrence_date)
+}
+
+/// Compare task estimates with the focus time logged against each task,
+/// optionally limited to a date range and to completed tasks
+#[tauri::command]
+pub fn get_estimate_report(
+    db: State<Database>,
+    user_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    completed_only: Option<bool>,
+) -> Result<EstimateReport, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND t.estimated_minutes IS NOT NULL
+               AND (?2 IS NULL OR t.date >= ?2) AND (?3 IS NULL OR t.date <= ?3)
+               AND (?4 = 0 OR t.completed = 1)
+             ORDER BY t.date ASC, t.start_time ASC",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let completed_only = if completed_only.unwrap_or(false) { 1 } else { 0 };
+    let tasks: Vec<Task> = stmt
+        .query_map(params![user_id, start_date, end_date, completed_only], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut by_category: BTreeMap<String, CategoryEstimate> = BTreeMap::new();
+    let mut estimates = Vec::new();
+
+    for task in tasks {
+        let estimated_seconds = task.estimated_minutes.unwrap_or(0) * 60;
+        let actual_seconds = task.focus_seconds;
+
+        let entry = by_category
+            .entry(task.category.as_str().to_string())
+            .or_insert_with(|| CategoryEstimate {
+                category: task.category.clone(),
+                task_count: 0,
+                estimated_seconds: 0,
+                actual_seconds: 0,
+                difference_seconds: 0,
+                accuracy_ratio: 0.0,
+            });
+        entry.task_count += 1;
+        entry.estimated_seconds += estimated_seconds;
+        entry.actual_seconds += actual_seconds;
+
+        estimates.push(TaskEstimate {
+            task_id: task.id,
+            title: task.title,
+            category: task.category,
+            date: task.date,
+            completed: task.completed,
+            estimated_seconds,
+            actual_seconds,
+            difference_seconds: actual_seconds - estimated_seconds,
+            accuracy_ratio: estimate_ratio(actual_seconds, estimated_seconds),
+        });
+    }
+
+    let categories = by_category
+        .into_values()
+        .map(|mut category| {
+            category.difference_seconds = category.actual_seconds - category.estimated_seconds;
+            category.accuracy_ratio = estimate_ratio(category.actual_seconds, category.estimated_seconds);
+            category
+        })
+        .collect();
+
+    Ok(EstimateReport {
+        tasks: estimates,
+        categories,
+    })
+}
+
+fn estimate_ratio(actual_seconds: i64, estimated_seconds: i64) -> f64 {
+    if estimated_seconds > 0 {
+        actual_seconds as f64 / estimated_seconds as f64
+    } else {
+        0.0
+    }
+}
+
+fn validate_estimate(estimated_minutes: Option<i64>) -> Result<(), String> {
+    if estimated_minutes.is_some_and(|minutes| minutes <= 0) {
+        return Err("Estimate must be at least one minute".to_string());
+    }
+    Ok(())
+}
+
+/// Tasks falling on days in `[start, end]`, with recurring tasks expanded
+/// into occurrences, ordered by date and start time
+fn tasks_in_range(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<Task>, String> {
+    let start_str = start.format("%Y-%m-%d").to_string();
+    let end_str = end.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND t.recurrence IS NULL AND t.date >= ?2 AND t.date <= ?3",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks: Vec<Task> = stmt
+        .query_map(params![user_id, start_str, end_str], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 AND t.recurrence IS NOT NULL AND t.date <= ?2",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let series: Vec<Task> = stmt
+        .query_map(params![user_id, end_str], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    for task in &series {
+        tasks.extend(expand_occurrences(conn, task, start, end)?);
+    }
+
+    tasks.sort_by(|a, b| (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time)));
+
+    Ok(tasks)
+}
+
+/// Every task of a user, recurring ones once as their series
+pub(crate) fn load_user_tasks(conn: &rusqlite::Connection, user_id: &str) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE t.user_id = ?1 ORDER BY t.date DESC, t.start_time ASC",
+            TASK_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks = stmt
+        .query_map(params![user_id], map_task)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(tasks)
+}
+
+/// Occurrences of a recurring task shown on days in `[from, to]`, including
+/// ones moved into the range and leaving out skipped or moved-away ones
+pub(crate) fn expand_occurrences(
+    conn: &rusqlite::Connection,
+    series: &Task,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Task>, String> {
+    let Some((rule, start)) = series_rule(series) else {
+        return Ok(Vec::new());
+    };
+
+    let overrides: HashMap<String, TaskOccurrence> = load_task_occurrences(conn, &series.id)?
+        .into_iter()
+        .map(|occurrence| (occurrence.occurrence_date.clone(), occurrence))
+        .collect();
+
+    let mut dates: BTreeSet<NaiveDate> = rule.occurrences(start, from, to).into_iter().collect();
+    for occurrence in overrides.values() {
+        let moved_in = occurrence
+            .date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .is_some_and(|date| date >= from && date <= to);
+        if let (true, Ok(original)) = (
+            moved_in,
+            NaiveDate::parse_from_str(&occurrence.occurrence_date, "%Y-%m-%d"),
+        ) {
+            if rule.is_occurrence(start, original) {
+                dates.insert(original);
+            }
+        }
+    }
+
+    let from_str = from.format("%Y-%m-%d").to_string();
+    let to_str = to.format("%Y-%m-%d").to_string();
+
+    let occurrences = dates
+        .into_iter()
+        .map(|date| {
+            let occurrence_date = date.format("%Y-%m-%d").to_string();
+            let stored = overrides.get(&occurrence_date);
+            occurrence_task(series, occurrence_date, stored)
+        })
+        .filter(|task| task.date >= from_str && task.date <= to_str)
+        .filter(|task| {
+            let skipped = task
+                .occurrence_date
+                .as_ref()
+                .and_then(|date| overrides.get(date))
+                .is_some_and(|stored| stored.skipped);
+            !skipped
+        })
+        .collect();
+
+    Ok(occurrences)
+}
+
+/// One occurrence of a series with any stored per-occurrence state applied
+fn occurrence_task(
+    series: &Task,
+    occurrence_date: String,
+    stored: Option<&TaskOccurrence>,
+) -> Task {
+    let mut task = series.clone();
+    task.date = stored
+        .and_then(|stored| stored.date.clone())
+        .unwrap_or_else(|| occurrence_date.clone());
+    if let Some(start_time) = stored.and_then(|stored| stored.start_time.clone()) {
+        task.start_time = start_time;
+    }
+    if let Some(end_time) = stored.and_then(|stored| stored.end_time.clone()) {
+        task.end_time = end_time;
+    }
+    task.completed = stored.is_some_and(|stored| stored.completed);
+    task.completed_at = stored.and_then(|stored| stored.completed_at.clone());
+    task.occurrence_date = Some(occurrence_date);
+    task
+}
+
+/// Parsed rule and first day of a recurring task; None for one-off tasks
+/// or unreadable stored rules
+fn series_rule(series: &Task) -> Option<(RecurrenceRule, NaiveDate)> {
+    let rule = RecurrenceRule::parse(series.recurrence.as_deref()?).ok()?;
+    let start = NaiveDate::parse_from_str(&series.date, "%Y-%m-%d").ok()?;
+    Some((rule, start))
+}
+
+/// Whether `date` is one of a recurring task's occurrences
+pub(crate) fn is_task_occurrence(series: &Task, date: &str) -> bool {
+    match (series_rule(series), NaiveDate::parse_from_str(date, "%Y-%m-%d")) {
+        (Some((rule, start)), Ok(date)) => rule.is_occurrence(start, date),
+        _ => false,
+    }
+}
+
+/// Skip one occurrence of a recurring task
+pub(crate) fn skip_occurrence(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: &str,
+) -> Result<(), String> {
+    require_occurrence(conn, task_id, occurrence_date)?;
+    ensure_occurrence_row(conn, task_id, occurrence_date)?;
+    conn.execute(
+        "UPDATE task_occurrences SET skipped = 1, updated_at = datetime('now')
+         WHERE task_id = ?1 AND occurrence_date = ?2",
+        params![task_id, occurrence_date],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Move one occurrence of a recurring task; times not given stay as they are
+pub(crate) fn move_occurrence(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: &str,
+    date: &str,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+) -> Result<Task, String> {
+    require_occurrence(conn, task_id, occurrence_date)?;
+    parse_task_date(date)?;
+    ensure_occurrence_row(conn, task_id, occurrence_date)?;
+    conn.execute(
+        "UPDATE task_occurrences
+         SET date = ?1, start_time = COALESCE(?2, start_time), end_time = COALESCE(?3, end_time),
+             skipped = 0, updated_at = datetime('now')
+         WHERE task_id = ?4 AND occurrence_date = ?5",
+        params![date, start_time, end_time, task_id, occurrence_date],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_occurrence_task(conn, task_id, occurrence_date)
+}
+
+/// Load a recurring task and check that `occurrence_date` is one of its occurrences
+fn require_occurrence(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: &str,
+) -> Result<Task, String> {
+    let series = get_task_by_id(conn, task_id)?;
+    let (rule, start) = series_rule(&series).ok_or_else(|| "Task is not recurring".to_string())?;
+
+    let date = parse_task_date(occurrence_date)?;
+    if !rule.is_occurrence(start, date) {
+        return Err(format!("Task does not occur on {}", occurrence_date));
+    }
+
+    Ok(series)
+}
+
+/// Mark a task, or one occurrence of a recurring task, completed or open,
+/// stamping `completed_at` and logging the change. Does nothing when the
+/// state is unchanged.
+pub(crate) fn set_task_completed(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: Option<&str>,
+    completed: bool,
+) -> Result<(), String> {
+    let completed_at = if completed { Some(chrono::Utc::now().to_rfc3339()) } else { None };
+
+    let task = match occurrence_date {
+        Some(occurrence_date) => {
+            ensure_occurrence_row(conn, task_id, occurrence_date)?;
+            let changed = conn
+                .execute(
+                    "UPDATE task_occurrences SET completed = ?1, completed_at = ?2, updated_at = datetime('now')
+                     WHERE task_id = ?3 AND occurrence_date = ?4 AND completed != ?1",
+                    params![if completed { 1 } else { 0 }, completed_at, task_id, occurrence_date],
+                )
+                .map_err(|e| e.to_string())?;
+            if changed == 0 {
+                return Ok(());
+            }
+            get_occurrence_task(conn, task_id, occurrence_date)?
+        }
+        None => {
+            let changed = conn
+                .execute(
+                    "UPDATE tasks SET completed = ?1, completed_at = ?2
+                     WHERE id = ?3 AND COALESCE(completed, 0) != ?1",
+                    params![if completed { 1 } else { 0 }, completed_at, task_id],
+                )
+                .map_err(|e| e.to_string())?;
+            if changed == 0 {
+                return Ok(());
+            }
+            get_task_by_id(conn, task_id)?
+        }
+    };
+
+    let event_type = if completed { TaskEventType::Completed } else { TaskEventType::Reopened };
+    record_task_event(conn, &task, occurrence_date, event_type)
+}
+
+fn ensure_occurrence_row(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR IGNORE INTO task_occurrences (id, task_id, occurrence_date) VALUES (?1, ?2, ?3)",
+        params![Uuid::new_v4().to_string(), task_id, occurrence_date],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn get_occurrence_task(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    occurrence_date: &str,
+) -> Result<Task, String> {
+    let series = get_task_by_id(conn, task_id)?;
+    let stored = conn
+        .query_row(
+            &format!("{} WHERE task_id = ?1 AND occurrence_date = ?2", OCCURRENCE_SELECT),
+            params![task_id, occurrence_date],
+            map_task_occurrence,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(occurrence_task(&series, occurrence_date.to_string(), stored.as_ref()))
+}
+
+const OCCURRENCE_SELECT: &str =
+    "SELECT id, task_id, occurrence_date, completed, skipped, date, start_time, end_time, completed_at FROM task_occurrences";
+
+fn map_task_occurrence(row: &rusqlite::Row) -> rusqlite::Result<TaskOccurrence> {
+    Ok(TaskOccurrence {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        occurrence_date: row.get(2)?,
+        completed: row.get::<_, i32>(3)? == 1,
+        skipped: row.get::<_, i32>(4)? == 1,
+        date: row.get(5)?,
+        start_time: row.get(6)?,
+        end_time: row.get(7)?,
+        completed_at: row.get(8)?,
+    })
+}
+
+fn load_task_occurrences(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+) -> Result<Vec<TaskOccurrence>, String> {
+    let mut stmt = conn
+        .prepare(&format!("{} WHERE task_id = ?1", OCCURRENCE_SELECT))
+        .map_err(|e| e.to_string())?;
+
+    let occurrences = stmt
+        .query_map(params![task_id], map_task_occurrence)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(occurrences)
+}
+
+/// Stored occurrence state for all of a user's recurring tasks
+pub(crate) fn load_user_task_occurrences(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<TaskOccurrence>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT o.id, o.task_id, o.occurrence_date, o.completed, o.skipped, o.date, o.start_time, o.end_time,
+                    o.completed_at
+             FROM task_occurrences o
+             JOIN tasks t ON t.id = o.task_id
+             WHERE t.user_id = ?1
+             ORDER BY o.task_id, o.occurrence_date",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let occurrences = stmt
+        .query_map(params![user_id], map_task_occurrence)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(occurrences)
+}
+
+/// Completed one-off tasks plus completed occurrences of recurring tasks.
+/// Checklist items are steps of a task and never count on their own.
+pub(crate) fn count_completed_tasks(conn: &rusqlite::Connection, user_id: &str) -> Result<i64, String> {
+    conn.query_row(
+        "SELECT
+            (SELECT COUNT(*) FROM tasks WHERE user_id = ?1 AND completed = 1 AND recurrence IS NULL)
+          + (SELECT COUNT(*) FROM task_occurrences o
+             JOIN tasks t ON t.id = o.task_id
+             WHERE t.user_id = ?1 AND o.completed = 1 AND o.skipped = 0)",
+        params![user_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Validate a recurrence rule and store it in a normalized form
+fn normalize_recurrence(recurrence: Option<&str>) -> Result<Option<String>, String> {
+    match recurrence.map(str::trim) {
+        Some(rule) if !rule.is_empty() => Ok(Some(RecurrenceRule::parse(rule)?.to_string())),
+        _ => Ok(None),
+    }
+}
+
+/// Checklist-driven completion applies to one-off tasks only, since the
+/// items of a recurring task are shared by all of its occurrences
+fn validate_auto_complete(auto_complete: bool, recurrence: Option<&str>) -> Result<(), String> {
+    if auto_complete && recurrence.is_some() {
+        return Err("Recurring tasks cannot complete automatically from their checklist".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_task_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", value))
+}
+
+/// Task columns plus the focus time of linked sessions; callers append
+/// WHERE/ORDER BY clauses against the `t` alias
+pub(crate) const TASK_SELECT: &str =
+    "SELECT t.id, t.user_id, t.title, t.category, t.date, t.start_time, t.end_time, t.completed, t.created_at,
+            COALESCE(f.focus_seconds, 0), COALESCE(f.session_count, 0), t.estimated_minutes, t.recurrence,
+            t.auto_complete, COALESCE(i.item_count, 0), COALESCE(i.items_completed, 0),
+            t.priority, g.tag_names,
+            (SELECT c.from_date FROM task_date_changes c WHERE c.task_id = t.id
+             ORDER BY c.changed_at ASC, c.rowid ASC LIMIT 1),
+            (SELECT COUNT(*) FROM task_date_changes c WHERE c.task_id = t.id AND c.to_date > c.from_date),
+            t.completed_at, t.ical_uid
+     FROM tasks t
+     LEFT JOIN (
+        SELECT task_id, SUM(duration_seconds) AS focus_seconds, COUNT(*) AS session_count
+        FROM focus_sessions
+        WHERE task_id IS NOT NULL
+        GROUP BY task_id
+     ) f ON f.task_id = t.id
+     LEFT JOIN (
+        SELECT task_id, COUNT(*) AS item_count, SUM(completed) AS items_completed
+        FROM task_items
+        GROUP BY task_id
+     ) i ON i.task_id = t.id
+     LEFT JOIN (
+        SELECT tt.task_id, group_concat(tg.name, char(31)) AS tag_names
+        FROM task_tags tt
+        JOIN tags tg ON tg.id = tt.tag_id
+        GROUP BY tt.task_id
+     ) g ON g.task_id = t.id";
+
+/// Map a row selected with `TASK_SELECT`
+pub(crate) fn map_task(row: &rusqlite::Row) -> rusqlite::Result<Task> {
+    let category_str: String = row.get(3)?;
+    Ok(Task {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        title: row.get(2)?,
+        category: TaskCategory::from_str(&category_str).unwrap_or(TaskCategory::ToDo),
+        date: row.get(4)?,
+        start_time: row.get(5)?,
+        end_time: row.get(6)?,
+        completed: row.get::<_, i32>(7)? == 1,
+        created_at: row.get(8)?,
+        focus_seconds: row.get(9)?,
+        session_count: row.get(10)?,
+        estimated_minutes: row.get(11)?,
+        recurrence: row.get(12)?,
+        occurrence_date: None,
+        auto_complete: row.get::<_, i32>(13)? == 1,
+        item_count: row.get(14)?,
+        items_completed: row.get(15)?,
+        priority: TaskPriority::from_str(&row.get::<_, String>(16)?).unwrap_or_default(),
+        tags: split_tag_names(row.get(17)?),
+        original_date: row.get(18)?,
+        postpone_count: row.get(19)?,
+        completed_at: row.get(20)?,
+        ical_uid: row.get(21)?,
+    })
+}
+
+/// Tag names joined by `TASK_SELECT`, sorted alphabetically
+fn split_tag_names(joined: Option<String>) -> Vec<String> {
+    let mut tags: Vec<String> = joined
+        .map(|joined| joined.split('\u{1f}').map(str::to_string).collect())
+        .unwrap_or_default();
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags
 }
 
 /// Helper function to get a task by ID
-fn get_task_by_id(conn: &rusqlite::Connection, task_id: &str) -> Result<Task, String> {
+pub(crate) fn get_task_by_id(conn: &rusqlite::Connection, task_id: &str) -> Result<Task, String> {
     conn.query_row(
-        "SELECT id, user_id, title, category, date, start_time, end_time, completed, created_at 
-         FROM tasks WHERE id = ?1",
+        &format!("{} WHERE t.id = ?1", TASK_SELECT),
         params![task_id],
-        |row| {
-            let category_str: String = row.get(3)?;
-            Ok(Task {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                title: row.get(2)?,
-                category: TaskCategory::from_str(&category_str).unwrap_or(TaskCategory::ToDo),
-                date: row.get(4)?,
-                start_time: row.get(5)?,
-                end_time: row.get(6)?,
-                completed: row.get::<_, i32>(7)? == 1,
-                created_at: row.get(8)?,
-            })
-        },
+        map_task,
     )
     .map_err(|e| e.to_string())
 }
@@ -0,0 +1,145 @@
+use crate::commands::clock::UserClock;
+use crate::commands::task::parse_task_date;
+use crate::db::Database;
+use crate::models::{DailyTaskCompletions, Task, TaskEvent, TaskEventType};
+use rusqlite::params;
+use std::collections::BTreeMap;
+use tauri::State;
+use uuid::Uuid;
+
+const TASK_EVENT_SELECT: &str =
+    "SELECT id, task_id, user_id, occurrence_date, event_type, title, occurred_at FROM task_events";
+
+/// Get the event log of a task, oldest first. Still available after the
+/// task is deleted.
+#[tauri::command]
+pub fn get_task_events(db: State<Database>, task_id: String) -> Result<Vec<TaskEvent>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE task_id = ?1 ORDER BY occurred_at ASC, rowid ASC",
+            TASK_EVENT_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let events = stmt
+        .query_map(params![task_id], map_task_event)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(events)
+}
+
+/// Count tasks (and occurrences of recurring tasks) completed on each day
+/// between `start_date` and `end_date` inclusive. Days follow the user's
+/// time zone and day start; tasks completed before completion times were
+/// recorded count on their own date. Days without completions are left out.
+#[tauri::command]
+pub fn get_task_completion_stats(
+    db: State<Database>,
+    user_id: String,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<DailyTaskCompletions>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let start = parse_task_date(&start_date)?;
+    let end = parse_task_date(&end_date)?;
+    if end < start {
+        return Err("End date must not be before start date".to_string());
+    }
+    // Compared as YYYY-MM-DD text, like the days below
+    let start_date = start.format("%Y-%m-%d").to_string();
+    let end_date = end.format("%Y-%m-%d").to_string();
+
+    let clock = UserClock::load(&conn, &user_id)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT completed_at, date FROM tasks
+             WHERE user_id = ?1 AND completed = 1 AND recurrence IS NULL
+             UNION ALL
+             SELECT o.completed_at, COALESCE(o.date, o.occurrence_date) FROM task_occurrences o
+             JOIN tasks t ON t.id = o.task_id
+             WHERE t.user_id = ?1 AND o.completed = 1 AND o.skipped = 0",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let completions: Vec<(Option<String>, String)> = stmt
+        .query_map(params![user_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut per_day: BTreeMap<String, i64> = BTreeMap::new();
+    for (completed_at, date) in completions {
+        let day = match completed_at {
+            Some(completed_at) => clock.date_from_iso(&completed_at)?,
+            None => date,
+        };
+        if day >= start_date && day <= end_date {
+            *per_day.entry(day).or_insert(0) += 1;
+        }
+    }
+
+    Ok(per_day
+        .into_iter()
+        .map(|(date, tasks_completed)| DailyTaskCompletions { date, tasks_completed })
+        .collect())
+}
+
+/// Append an entry to the task event log
+pub(crate) fn record_task_event(
+    conn: &rusqlite::Connection,
+    task: &Task,
+    occurrence_date: Option<&str>,
+    event_type: TaskEventType,
+) -> Result<(), String> {
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO task_events (id, task_id, user_id, occurrence_date, event_type, title, occurred_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![id, task.id, task.user_id, occurrence_date, event_type.as_str(), task.title, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Load the event log of all of a user's tasks
+pub(crate) fn load_user_task_events(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<TaskEvent>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE user_id = ?1 ORDER BY occurred_at ASC, rowid ASC",
+            TASK_EVENT_SELECT
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let events = stmt
+        .query_map(params![user_id], map_task_event)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(events)
+}
+
+fn map_task_event(row: &rusqlite::Row) -> rusqlite::Result<TaskEvent> {
+    let event_type: String = row.get(4)?;
+    Ok(TaskEvent {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        user_id: row.get(2)?,
+        occurrence_date: row.get(3)?,
+        event_type: TaskEventType::from_str(&event_type).unwrap_or(TaskEventType::Updated),
+        title: row.get(5)?,
+        occurred_at: row.get(6)?,
+    })
+}
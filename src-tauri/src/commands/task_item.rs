@@ -0,0 +1,253 @@
+use crate::commands::task::{get_task_by_id, set_task_completed};
+use crate::db::Database;
+use crate::models::{Task, TaskItem};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashSet;
+use tauri::State;
+use uuid::Uuid;
+
+/// Get a task's checklist items in order
+#[tauri::command]
+pub fn get_task_items(db: State<Database>, task_id: String) -> Result<Vec<TaskItem>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    load_task_items(&conn, &task_id)
+}
+
+/// Add an item to the end of a task's checklist
+#[tauri::command]
+pub fn add_task_item(db: State<Database>, task_id: String, title: String) -> Result<TaskItem, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    get_task_by_id(&conn, &task_id)?;
+    let title = normalize_item_title(&title)?;
+
+    let position: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM task_items WHERE task_id = ?1",
+            params![task_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO task_items (id, task_id, title, completed, position, created_at, updated_at)
+         VALUES (?1, ?2, ?3, 0, ?4, ?5, ?5)",
+        params![id, task_id, title, position, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // A new open item reopens a checklist-driven task
+    sync_task_completion(&conn, &task_id)?;
+
+    get_task_item_by_id(&conn, &id)
+}
+
+/// Rename a checklist item
+#[tauri::command]
+pub fn rename_task_item(db: State<Database>, item_id: String, title: String) -> Result<TaskItem, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    get_task_item_by_id(&conn, &item_id)?;
+    let title = normalize_item_title(&title)?;
+
+    conn.execute(
+        "UPDATE task_items SET title = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![title, item_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_task_item_by_id(&conn, &item_id)
+}
+
+/// Toggle a checklist item, returning its task so callers see any
+/// completion change derived from the checklist
+#[tauri::command]
+pub fn toggle_task_item(db: State<Database>, item_id: String) -> Result<Task, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let item = get_task_item_by_id(&conn, &item_id)?;
+
+    conn.execute(
+        "UPDATE task_items SET completed = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![if item.completed { 0 } else { 1 }, item_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    sync_task_completion(&conn, &item.task_id)?;
+
+    get_task_by_id(&conn, &item.task_id)
+}
+
+/// Reorder a task's checklist; `item_ids` must list every item exactly once
+#[tauri::command]
+pub fn reorder_task_items(
+    db: State<Database>,
+    task_id: String,
+    item_ids: Vec<String>,
+) -> Result<Vec<TaskItem>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let existing: HashSet<String> = load_task_items(&conn, &task_id)?
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    let requested: HashSet<String> = item_ids.iter().cloned().collect();
+    if requested.len() != item_ids.len() || requested != existing {
+        return Err("Item order must list each of the task's items exactly once".to_string());
+    }
+
+    for (position, item_id) in item_ids.iter().enumerate() {
+        conn.execute(
+            "UPDATE task_items SET position = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![position as i64, item_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    load_task_items(&conn, &task_id)
+}
+
+/// Delete a checklist item
+#[tauri::command]
+pub fn delete_task_item(db: State<Database>, item_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let Some(item) = conn
+        .query_row(
+            "SELECT id, task_id, title, completed, position, created_at FROM task_items WHERE id = ?1",
+            params![item_id],
+            map_task_item,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+
+    conn.execute("DELETE FROM task_items WHERE id = ?1", params![item_id])
+        .map_err(|e| e.to_string())?;
+
+    // Close the gap so positions stay 0-based and contiguous
+    conn.execute(
+        "UPDATE task_items SET position = position - 1 WHERE task_id = ?1 AND position > ?2",
+        params![item.task_id, item.position],
+    )
+    .map_err(|e| e.to_string())?;
+
+    sync_task_completion(&conn, &item.task_id)?;
+
+    Ok(())
+}
+
+/// For a checklist-driven task with items, mark it complete exactly when all
+/// of its items are. Other tasks keep their manual completion state.
+pub(crate) fn sync_task_completion(conn: &rusqlite::Connection, task_id: &str) -> Result<(), String> {
+    let all_done: Option<bool> = conn
+        .query_row(
+            "SELECT NOT EXISTS(SELECT 1 FROM task_items WHERE task_id = tasks.id AND completed = 0)
+             FROM tasks
+             WHERE id = ?1
+               AND auto_complete = 1
+               AND recurrence IS NULL
+               AND EXISTS(SELECT 1 FROM task_items WHERE task_id = tasks.id)",
+            params![task_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match all_done {
+        Some(completed) => set_task_completed(conn, task_id, None, completed),
+        None => Ok(()),
+    }
+}
+
+/// Tick off or reopen every item of a task
+pub(crate) fn set_task_items_completed(
+    conn: &rusqlite::Connection,
+    task_id: &str,
+    completed: bool,
+) -> Result<(), String> {
+    conn.execute(
+        "UPDATE task_items SET completed = ?1, updated_at = datetime('now')
+         WHERE task_id = ?2 AND completed != ?1",
+        params![if completed { 1 } else { 0 }, task_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn load_task_items(conn: &rusqlite::Connection, task_id: &str) -> Result<Vec<TaskItem>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, task_id, title, completed, position, created_at
+             FROM task_items
+             WHERE task_id = ?1
+             ORDER BY position ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let items = stmt
+        .query_map(params![task_id], map_task_item)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(items)
+}
+
+/// Load every checklist item of a user's tasks
+pub(crate) fn load_user_task_items(conn: &rusqlite::Connection, user_id: &str) -> Result<Vec<TaskItem>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT i.id, i.task_id, i.title, i.completed, i.position, i.created_at
+             FROM task_items i
+             JOIN tasks t ON t.id = i.task_id
+             WHERE t.user_id = ?1
+             ORDER BY i.task_id, i.position ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let items = stmt
+        .query_map(params![user_id], map_task_item)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(items)
+}
+
+fn get_task_item_by_id(conn: &rusqlite::Connection, item_id: &str) -> Result<TaskItem, String> {
+    conn.query_row(
+        "SELECT id, task_id, title, completed, position, created_at FROM task_items WHERE id = ?1",
+        params![item_id],
+        map_task_item,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Task item not found".to_string())
+}
+
+fn map_task_item(row: &rusqlite::Row) -> rusqlite::Result<TaskItem> {
+    Ok(TaskItem {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        title: row.get(2)?,
+        completed: row.get::<_, i32>(3)? == 1,
+        position: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn normalize_item_title(title: &str) -> Result<String, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Item title cannot be empty".to_string());
+    }
+    Ok(title.to_string())
+}
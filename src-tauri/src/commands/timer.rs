@@ -0,0 +1,276 @@
+use crate::commands::session::{insert_focus_session, validate_session_task};
+use crate::db::Database;
+use crate::models::{
+    ActiveSession, CreateFocusSessionRequest, FocusSession, SessionSegment, TimerStatus,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+use uuid::Uuid;
+
+/// Start a backend-timed focus session for a user, optionally for one of their tasks
+#[tauri::command]
+pub fn start_focus_timer(
+    db: State<Database>,
+    user_id: String,
+    category: Option<String>,
+    notes: Option<String>,
+    task_id: Option<String>,
+) -> Result<ActiveSession, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    if get_active_session_internal(&conn, &user_id, Utc::now())?.is_some() {
+        return Err("A focus session is already in progress".to_string());
+    }
+    if let Some(ref task_id) = task_id {
+        validate_session_task(&conn, &user_id, task_id)?;
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = format_timer_time(Utc::now());
+
+    conn.execute(
+        "INSERT INTO active_sessions (id, user_id, status, started_at, resumed_at, accumulated_seconds, category, notes, task_id)
+         VALUES (?1, ?2, ?3, ?4, ?4, 0, ?5, ?6, ?7)",
+        params![id, user_id, TimerStatus::Running.as_str(), now, category, notes, task_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    require_active_session(&conn, &user_id, Utc::now())
+}
+
+/// Get the running or paused session for a user, if any
+#[tauri::command]
+pub fn get_active_session(
+    db: State<Database>,
+    user_id: String,
+) -> Result<Option<ActiveSession>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    get_active_session_internal(&conn, &user_id, Utc::now())
+}
+
+/// Pause the running session, banking the time focused so far
+#[tauri::command]
+pub fn pause_focus_timer(db: State<Database>, user_id: String) -> Result<ActiveSession, String> {
+    let mut conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let now = Utc::now();
+
+    let session = require_active_session(&tx, &user_id, now)?;
+    if session.status != TimerStatus::Running {
+        return Err("Focus session is not running".to_string());
+    }
+
+    if let Some(segment) = current_stretch(&session, now) {
+        let position = load_active_segments(&tx, &session.id)?.len() as i64;
+        tx.execute(
+            "INSERT INTO active_session_segments (id, active_session_id, position, started_at, ended_at, duration_seconds)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                session.id,
+                position,
+                segment.started_at,
+                segment.ended_at,
+                segment.duration_seconds
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.execute(
+        "UPDATE active_sessions
+         SET status = ?1, resumed_at = NULL, accumulated_seconds = ?2, updated_at = datetime('now')
+         WHERE id = ?3",
+        params![TimerStatus::Paused.as_str(), session.elapsed_seconds, session.id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let paused = require_active_session(&tx, &user_id, now)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(paused)
+}
+
+/// Resume a paused session
+#[tauri::command]
+pub fn resume_focus_timer(db: State<Database>, user_id: String) -> Result<ActiveSession, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let now = Utc::now();
+
+    let session = require_active_session(&conn, &user_id, now)?;
+    if session.status != TimerStatus::Paused {
+        return Err("Focus session is not paused".to_string());
+    }
+
+    conn.execute(
+        "UPDATE active_sessions
+         SET status = ?1, resumed_at = ?2, updated_at = datetime('now')
+         WHERE id = ?3",
+        params![TimerStatus::Running.as_str(), format_timer_time(now), session.id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    require_active_session(&conn, &user_id, now)
+}
+
+/// Stop the session and record it as a finished focus session
+#[tauri::command]
+pub fn stop_focus_timer(
+    db: State<Database>,
+    user_id: String,
+    notes: Option<String>,
+) -> Result<FocusSession, String> {
+    let mut conn = db.conn.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let now = Utc::now();
+
+    let session = require_active_session(&tx, &user_id, now)?;
+
+    let mut segments = load_active_segments(&tx, &session.id)?;
+    if session.status == TimerStatus::Running {
+        segments.extend(current_stretch(&session, now));
+    }
+
+    let finished = insert_focus_session(
+        &tx,
+        CreateFocusSessionRequest {
+            user_id,
+            duration_seconds: session.elapsed_seconds,
+            started_at: session.started_at,
+            ended_at: format_timer_time(now),
+            category: session.category,
+            notes: notes.or(session.notes),
+            segments: Some(segments),
+            subject_id: None,
+            task_id: session.task_id,
+        },
+    )?;
+
+    tx.execute("DELETE FROM active_sessions WHERE id = ?1", params![session.id])
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(finished)
+}
+
+/// Discard the session without recording it
+#[tauri::command]
+pub fn abandon_focus_timer(db: State<Database>, user_id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "DELETE FROM active_sessions WHERE user_id = ?1",
+        params![user_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Timestamps use the same shape as the webview's `Date.toISOString()`
+fn format_timer_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn require_active_session(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> Result<ActiveSession, String> {
+    get_active_session_internal(conn, user_id, now)?
+        .ok_or_else(|| "No focus session in progress".to_string())
+}
+
+/// Elapsed time of a running session is measured up to `now`
+fn get_active_session_internal(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<ActiveSession>, String> {
+    let session = conn
+        .query_row(
+            "SELECT id, user_id, status, started_at, resumed_at, accumulated_seconds, category, notes, task_id
+             FROM active_sessions WHERE user_id = ?1",
+            params![user_id],
+            |row| {
+                let status_str: String = row.get(2)?;
+                let status = TimerStatus::from_str(&status_str).ok_or_else(|| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        2,
+                        rusqlite::types::Type::Text,
+                        format!("Invalid timer status: {}", status_str).into(),
+                    )
+                })?;
+                let accumulated_seconds: i64 = row.get(5)?;
+                Ok(ActiveSession {
+                    id: row.get(0)?,
+                    user_id: row.get(1)?,
+                    status,
+                    started_at: row.get(3)?,
+                    resumed_at: row.get(4)?,
+                    accumulated_seconds,
+                    elapsed_seconds: accumulated_seconds,
+                    category: row.get(6)?,
+                    notes: row.get(7)?,
+                    task_id: row.get(8)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(session.map(|mut session| {
+        if session.status == TimerStatus::Running {
+            session.elapsed_seconds += running_seconds(session.resumed_at.as_deref(), now);
+        }
+        session
+    }))
+}
+
+/// Seconds between the start of the current running stretch and `now`
+fn running_seconds(resumed_at: Option<&str>, now: DateTime<Utc>) -> i64 {
+    resumed_at
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|resumed| (now - resumed.with_timezone(&Utc)).num_seconds())
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// The stretch from the last start/resume until `now`
+fn current_stretch(session: &ActiveSession, now: DateTime<Utc>) -> Option<SessionSegment> {
+    let resumed_at = session.resumed_at.clone()?;
+    Some(SessionSegment {
+        duration_seconds: running_seconds(Some(&resumed_at), now),
+        started_at: resumed_at,
+        ended_at: format_timer_time(now),
+    })
+}
+
+fn load_active_segments(
+    conn: &rusqlite::Connection,
+    active_session_id: &str,
+) -> Result<Vec<SessionSegment>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT started_at, ended_at, duration_seconds
+             FROM active_session_segments
+             WHERE active_session_id = ?1
+             ORDER BY position ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let segments = stmt
+        .query_map(params![active_session_id], |row| {
+            Ok(SessionSegment {
+                started_at: row.get(0)?,
+                ended_at: row.get(1)?,
+                duration_seconds: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(segments)
+}
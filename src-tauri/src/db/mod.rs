@@ -24,13 +24,25 @@ pub fn init_database(db_path: &PathBuf) -> Result<Connection> {
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
     
     // Run migrations
-    run_migrations(&conn)?;
+    let migrated = run_migrations(&conn)?;
+
+    // Schema changes may affect how stats are derived, so refresh the cache
+    if migrated {
+        if let Err(e) = crate::commands::session::rebuild_all_daily_stats(&conn) {
+            log::warn!("Failed to rebuild daily stats after migration: {}", e);
+        }
+    }
+
+    // Getters never spend or earn tokens, so settle the days passed since the last run
+    if let Err(e) = crate::commands::streak::apply_all_streak_freezes(&conn) {
+        log::warn!("Failed to apply streak freezes: {}", e);
+    }
     
     Ok(conn)
 }
 
-/// Run database migrations
-fn run_migrations(conn: &Connection) -> Result<()> {
+/// Run database migrations, returning whether any were applied
+fn run_migrations(conn: &Connection) -> Result<bool> {
     // Create migration tracking table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS _migrations (
@@ -46,8 +58,25 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         ("002_add_avatar_path", include_str!("../../migrations/002_add_avatar_path.sql")),
         ("003_add_achievement_seen_at", include_str!("../../migrations/003_add_achievement_seen_at.sql")),
         ("004_add_app_settings", include_str!("../../migrations/004_add_app_settings.sql")),
+        ("005_add_active_sessions", include_str!("../../migrations/005_add_active_sessions.sql")),
+        ("006_add_session_segments", include_str!("../../migrations/006_add_session_segments.sql")),
+        ("007_add_streak_freezes", include_str!("../../migrations/007_add_streak_freezes.sql")),
+        ("008_add_daily_goals", include_str!("../../migrations/008_add_daily_goals.sql")),
+        ("009_add_period_goals", include_str!("../../migrations/009_add_period_goals.sql")),
+        ("010_add_subjects", include_str!("../../migrations/010_add_subjects.sql")),
+        ("011_add_session_tasks", include_str!("../../migrations/011_add_session_tasks.sql")),
+        ("012_add_task_estimates", include_str!("../../migrations/012_add_task_estimates.sql")),
+        ("013_add_task_recurrence", include_str!("../../migrations/013_add_task_recurrence.sql")),
+        ("014_add_task_items", include_str!("../../migrations/014_add_task_items.sql")),
+        ("015_add_task_priorities_tags", include_str!("../../migrations/015_add_task_priorities_tags.sql")),
+        ("016_add_task_date_changes", include_str!("../../migrations/016_add_task_date_changes.sql")),
+        ("017_add_task_reminders", include_str!("../../migrations/017_add_task_reminders.sql")),
+        ("018_add_task_completion_history", include_str!("../../migrations/018_add_task_completion_history.sql")),
+        ("019_add_task_ical_uid", include_str!("../../migrations/019_add_task_ical_uid.sql")),
+        ("020_add_task_reminder_index", include_str!("../../migrations/020_add_task_reminder_index.sql")),
     ];
 
+    let mut applied = false;
     for (name, sql) in migrations {
         let migration_exists: bool = conn.query_row(
             "SELECT EXISTS(SELECT 1 FROM _migrations WHERE name = ?1)",
@@ -80,9 +109,10 @@ fn run_migrations(conn: &Connection) -> Result<()> {
             "INSERT INTO _migrations (name) VALUES (?1)",
             params![name],
         )?;
+        applied = true;
     }
     
-    Ok(())
+    Ok(applied)
 }
 
 fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
@@ -1,6 +1,7 @@
 mod commands;
 mod db;
 mod models;
+mod reminders;
 
 use db::Database;
 use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
@@ -8,6 +9,7 @@ use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // Initialize logging in debug mode
             if cfg!(debug_assertions) {
@@ -30,6 +32,9 @@ pub fn run() {
             // Store database in app state
             app.manage(database);
 
+            // Fire task reminders in the background
+            reminders::start_reminder_scheduler(app.handle().clone());
+
             // Create splashscreen window
             let splash_url = WebviewUrl::App("splash.html".into());
             let _ = WebviewWindowBuilder::new(app.handle(), "splashscreen", splash_url)
@@ -59,30 +64,104 @@ pub fn run() {
             commands::delete_user,
             // Session commands
             commands::create_focus_session,
+            commands::log_manual_session,
+            commands::find_overlapping_sessions,
             commands::get_focus_sessions,
+            commands::update_focus_session,
+            commands::delete_focus_session,
             commands::get_daily_stats,
             commands::get_heatmap_data,
+            commands::get_category_stats,
             commands::get_user_stats,
+            commands::rebuild_daily_stats,
+            // Focus timer commands
+            commands::start_focus_timer,
+            commands::get_active_session,
+            commands::pause_focus_timer,
+            commands::resume_focus_timer,
+            commands::stop_focus_timer,
+            commands::abandon_focus_timer,
             // Task commands
             commands::create_task,
             commands::get_tasks,
+            commands::query_tasks,
             commands::update_task,
             commands::delete_task,
             commands::toggle_task_completion,
+            commands::skip_task_occurrence,
+            commands::move_task_occurrence,
+            commands::restore_task_occurrence,
+            commands::get_estimate_report,
+            commands::get_overdue_tasks,
+            commands::roll_over_tasks,
+            commands::get_postponement_report,
+            // Task checklist commands
+            commands::get_task_items,
+            commands::add_task_item,
+            commands::rename_task_item,
+            commands::toggle_task_item,
+            commands::reorder_task_items,
+            commands::delete_task_item,
+            // Task history commands
+            commands::get_task_events,
+            commands::get_task_completion_stats,
             // Achievement commands
             commands::get_achievements,
             commands::get_unseen_achievements_count,
             commands::mark_achievements_seen,
             commands::unlock_achievement,
             commands::check_and_unlock_achievements,
+            // Streak freeze commands
+            commands::get_streak_freezes,
+            commands::grant_streak_freezes,
+            // Goal commands
+            commands::get_daily_goals,
+            commands::set_daily_goal,
+            commands::delete_daily_goal,
+            commands::get_goal_progress,
+            commands::create_period_goal,
+            commands::get_period_goals,
+            commands::delete_period_goal,
+            commands::get_period_goal_progress,
+            // Subject commands
+            commands::create_subject,
+            commands::get_subjects,
+            commands::update_subject,
+            commands::delete_subject,
+            commands::merge_subjects,
+            // Tag commands
+            commands::create_tag,
+            commands::get_tags,
+            commands::update_tag,
+            commands::delete_tag,
+            // Reminder commands
+            commands::get_active_reminders,
+            commands::snooze_reminder,
+            commands::dismiss_reminder,
             // Settings commands
             commands::get_setting,
             commands::set_setting,
             commands::get_all_settings,
             commands::delete_setting,
+            commands::get_time_zone,
+            commands::set_time_zone,
+            commands::get_day_start_hour,
+            commands::set_day_start_hour,
+            commands::get_reminder_lead_minutes,
+            commands::set_reminder_lead_minutes,
             // Data export commands
             commands::export_all_data,
             commands::import_data,
+            // Calendar (ICS) commands
+            commands::import_ics,
+            commands::export_ics,
+            commands::export_sessions_ics,
+            // CSV commands
+            commands::export_sessions_csv,
+            commands::export_tasks_csv,
+            commands::export_daily_stats_csv,
+            commands::get_csv_headers,
+            commands::import_sessions_csv,
             // App lifecycle commands
             commands::close_splashscreen,
         ])
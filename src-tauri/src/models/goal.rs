@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// Daily focus goal (weekday-specific or the default for all days)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyGoal {
+    pub id: String,
+    pub user_id: String,
+    pub weekday: Option<i64>,   // 0 = Sunday ... 6 = Saturday, None = every day
+    pub target_minutes: i64,
+}
+
+/// Progress toward the daily goal on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub date: String,
+    pub target_seconds: Option<i64>,   // None when no goal applies to the day
+    pub focus_seconds: i64,
+    pub met: bool,
+}
+
+/// Length of a period goal
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum GoalPeriod {
+    #[serde(rename = "week")]
+    Week,
+    #[serde(rename = "month")]
+    Month,
+}
+
+impl GoalPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalPeriod::Week => "week",
+            GoalPeriod::Month => "month",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "week" => Some(GoalPeriod::Week),
+            "month" => Some(GoalPeriod::Month),
+            _ => None,
+        }
+    }
+}
+
+/// Weekly or monthly focus goal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodGoal {
+    pub id: String,
+    pub user_id: String,
+    pub period: GoalPeriod,
+    pub target_seconds: i64,
+    pub category: Option<String>,   // None counts focus time in every category
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePeriodGoalRequest {
+    pub user_id: String,
+    pub period: GoalPeriod,
+    pub target_seconds: i64,
+    pub category: Option<String>,
+}
+
+/// Progress toward a period goal in one week or month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodGoalProgress {
+    pub label: String,   // "2026-W41" for weeks, "2026-10" for months
+    pub start_date: String,
+    pub end_date: String,
+    pub target_seconds: i64,
+    pub focus_seconds: i64,
+    pub met: bool,
+}
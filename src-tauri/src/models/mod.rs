@@ -2,8 +2,16 @@ pub mod user;
 pub mod session;
 pub mod task;
 pub mod achievement;
+pub mod goal;
+pub mod subject;
+pub mod tag;
+pub mod reminder;
 
 pub use user::*;
 pub use session::*;
 pub use task::*;
 pub use achievement::*;
+pub use goal::*;
+pub use subject::*;
+pub use tag::*;
+pub use reminder::*;
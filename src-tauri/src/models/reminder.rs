@@ -0,0 +1,49 @@
+use crate::models::TaskCategory;
+use serde::{Deserialize, Serialize};
+
+/// Delivery state of a task reminder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ReminderStatus {
+    #[serde(rename = "delivered")]
+    Delivered,
+    #[serde(rename = "snoozed")]
+    Snoozed,
+    #[serde(rename = "dismissed")]
+    Dismissed,
+}
+
+impl ReminderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReminderStatus::Delivered => "delivered",
+            ReminderStatus::Snoozed => "snoozed",
+            ReminderStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "delivered" => Some(ReminderStatus::Delivered),
+            "snoozed" => Some(ReminderStatus::Snoozed),
+            "dismissed" => Some(ReminderStatus::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+/// Reminder for a Reminder/Event task; also the payload of the
+/// `task-reminder` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReminder {
+    pub id: String,
+    pub task_id: String,
+    pub user_id: String,
+    pub title: String,
+    pub category: TaskCategory,
+    pub occurrence_date: Option<String>,   // set for occurrences of recurring tasks
+    pub due_at: String,                    // task start (RFC3339, UTC)
+    pub status: ReminderStatus,
+    pub delivered_at: Option<String>,
+    pub snoozed_until: Option<String>,
+}
@@ -9,9 +9,22 @@ pub struct FocusSession {
     pub duration_seconds: i64,
     pub started_at: String,
     pub ended_at: String,
-    pub category: Option<String>,
+    pub category: Option<String>,   // Name of the subject, kept in sync with subject_id
     pub notes: Option<String>,
     pub created_at: String,
+    #[serde(default)]
+    pub segments: Vec<SessionSegment>,
+    pub subject_id: Option<String>,
+    pub task_id: Option<String>,   // task the session was spent on
+}
+
+/// Focused stretch within a session (pauses are the gaps between segments)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSegment {
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_seconds: i64,
 }
 
 /// Create focus session request
@@ -22,8 +35,25 @@ pub struct CreateFocusSessionRequest {
     pub duration_seconds: i64,
     pub started_at: String,
     pub ended_at: String,
+    pub category: Option<String>,   // Matched to a subject by name, creating one if needed
+    pub notes: Option<String>,
+    pub segments: Option<Vec<SessionSegment>>,
+    pub subject_id: Option<String>,   // Takes precedence over category
+    pub task_id: Option<String>,
+}
+
+/// Update focus session request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFocusSessionRequest {
+    pub duration_seconds: Option<i64>,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
     pub category: Option<String>,
     pub notes: Option<String>,
+    pub segments: Option<Vec<SessionSegment>>,
+    pub subject_id: Option<String>,
+    pub task_id: Option<String>,   // an empty string unlinks the task
 }
 
 /// Daily stats model
@@ -35,6 +65,29 @@ pub struct DailyStats {
     pub session_count: i64,
 }
 
+/// Bucket size for grouped statistics
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum StatsGranularity {
+    #[serde(rename = "day")]
+    Day,
+    #[serde(rename = "week")]
+    Week,
+    #[serde(rename = "month")]
+    Month,
+}
+
+/// Focus time for one category within one day, week or month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryStats {
+    pub period: String,        // "2026-10-17", "2026-W42" or "2026-10"
+    pub period_start: String,  // first day of the period (YYYY-MM-DD)
+    pub subject_id: Option<String>,
+    pub category: Option<String>,   // None for uncategorized sessions
+    pub total_focus_seconds: i64,
+    pub session_count: i64,    // sessions that started in the period
+}
+
 /// User statistics summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -44,6 +97,21 @@ pub struct UserStats {
     pub current_streak: i64,
     pub longest_streak: i64,
     pub tasks_completed: i64,
+    pub streak_freezes_available: i64,
+    pub frozen_dates: Vec<String>,   // days covered by a streak freeze
+    pub goal_current_streak: i64,    // consecutive days the daily goal was met
+    pub goal_longest_streak: i64,
+}
+
+/// Streak freeze token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreakFreeze {
+    pub id: String,
+    pub source: String,             // "granted" or "earned"
+    pub earned_for: Option<String>, // streak milestone day for earned tokens
+    pub granted_at: String,
+    pub used_on: Option<String>,    // missed day the token covered
 }
 
 /// Heatmap data point
@@ -53,3 +121,45 @@ pub struct HeatmapData {
     pub date: String,
     pub value: i64,  // focus time in minutes
 }
+
+/// Live timer status enum
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TimerStatus {
+    #[serde(rename = "running")]
+    Running,
+    #[serde(rename = "paused")]
+    Paused,
+}
+
+impl TimerStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimerStatus::Running => "running",
+            TimerStatus::Paused => "paused",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "running" => Some(TimerStatus::Running),
+            "paused" => Some(TimerStatus::Paused),
+            _ => None,
+        }
+    }
+}
+
+/// Focus session currently being timed by the backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSession {
+    pub id: String,
+    pub user_id: String,
+    pub status: TimerStatus,
+    pub started_at: String,
+    pub resumed_at: Option<String>,      // start of the current running stretch
+    pub accumulated_seconds: i64,        // focused time before the current stretch
+    pub elapsed_seconds: i64,            // total focused time as of now
+    pub category: Option<String>,
+    pub notes: Option<String>,
+    pub task_id: Option<String>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Subject a focus session is spent on (e.g. "Math")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subject {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub archived: bool,
+    pub created_at: String,
+}
+
+/// Create subject request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubjectRequest {
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// Update subject request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSubjectRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub archived: Option<bool>,
+}
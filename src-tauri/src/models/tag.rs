@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// User-defined label for tasks (e.g. "exam-prep")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: String,
+}
+
+/// Create tag request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagRequest {
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+/// Update tag request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
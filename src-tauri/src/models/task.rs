@@ -1,33 +1,90 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-/// Task category enum
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Task category: one of the built-in kinds or a user-defined name.
+/// Serialized as the plain category name.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TaskCategory {
-    #[serde(rename = "To Do")]
     ToDo,
-    #[serde(rename = "Event")]
     Event,
-    #[serde(rename = "Reminder")]
     Reminder,
+    Custom(String),
 }
 
 impl TaskCategory {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             TaskCategory::ToDo => "To Do",
             TaskCategory::Event => "Event",
             TaskCategory::Reminder => "Reminder",
+            TaskCategory::Custom(name) => name,
         }
     }
     
+    /// Built-in names match case-insensitively; anything else non-blank is a
+    /// custom category
+    pub fn from_str(s: &str) -> Option<Self> {
+        let name = s.trim();
+        match name.to_lowercase().as_str() {
+            "" => None,
+            "to do" => Some(TaskCategory::ToDo),
+            "event" => Some(TaskCategory::Event),
+            "reminder" => Some(TaskCategory::Reminder),
+            _ => Some(TaskCategory::Custom(name.to_string())),
+        }
+    }
+}
+
+impl Serialize for TaskCategory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        TaskCategory::from_str(&name).ok_or_else(|| de::Error::custom("Task category cannot be empty"))
+    }
+}
+
+/// Task priority, lowest first
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum TaskPriority {
+    #[serde(rename = "low")]
+    Low,
+    #[serde(rename = "normal")]
+    #[default]
+    Normal,
+    #[serde(rename = "high")]
+    High,
+    #[serde(rename = "urgent")]
+    Urgent,
+}
+
+impl TaskPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::Low => "low",
+            TaskPriority::Normal => "normal",
+            TaskPriority::High => "high",
+            TaskPriority::Urgent => "urgent",
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
-            "To Do" => Some(TaskCategory::ToDo),
-            "Event" => Some(TaskCategory::Event),
-            "Reminder" => Some(TaskCategory::Reminder),
+            "low" => Some(TaskPriority::Low),
+            "normal" => Some(TaskPriority::Normal),
+            "high" => Some(TaskPriority::High),
+            "urgent" => Some(TaskPriority::Urgent),
             _ => None,
         }
     }
+
+    /// Position in the low-to-urgent order, for sorting
+    pub fn rank(&self) -> u8 {
+        *self as u8
+    }
 }
 
 /// Task model
@@ -43,6 +100,31 @@ pub struct Task {
     pub end_time: String,     // HH:MM format
     pub completed: bool,
     pub created_at: String,
+    #[serde(default)]
+    pub focus_seconds: i64,   // focus time of sessions linked to the task
+    #[serde(default)]
+    pub session_count: i64,
+    pub estimated_minutes: Option<i64>,
+    pub recurrence: Option<String>,        // RRULE, e.g. "FREQ=WEEKLY;BYDAY=MO,WE"
+    pub occurrence_date: Option<String>,   // original date when this is one occurrence of a recurring task
+    #[serde(default)]
+    pub auto_complete: bool,               // completion follows the checklist items
+    #[serde(default)]
+    pub item_count: i64,
+    #[serde(default)]
+    pub items_completed: i64,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub tags: Vec<String>,                 // tag names, alphabetical
+    #[serde(default)]
+    pub original_date: Option<String>,     // date before the first move, None if never moved
+    #[serde(default)]
+    pub postpone_count: i64,               // moves to a later date
+    #[serde(default)]
+    pub completed_at: Option<String>,      // RFC3339, None while open
+    #[serde(default)]
+    pub ical_uid: Option<String>,          // UID of the calendar entry the task was imported from
 }
 
 /// Create task request
@@ -55,6 +137,11 @@ pub struct CreateTaskRequest {
     pub date: String,
     pub start_time: String,
     pub end_time: String,
+    pub estimated_minutes: Option<i64>,
+    pub recurrence: Option<String>,
+    pub auto_complete: Option<bool>,
+    pub priority: Option<TaskPriority>,   // defaults to normal
+    pub tags: Option<Vec<String>>,        // tag names; unknown tags are created
 }
 
 /// Update task request
@@ -67,4 +154,246 @@ pub struct UpdateTaskRequest {
     pub start_time: Option<String>,
     pub end_time: Option<String>,
     pub completed: Option<bool>,
+    pub estimated_minutes: Option<i64>,   // 0 clears the estimate
+    pub recurrence: Option<String>,       // an empty string stops the task recurring
+    pub auto_complete: Option<bool>,
+    pub priority: Option<TaskPriority>,
+    pub tags: Option<Vec<String>>,        // replaces the task's tags
+}
+
+/// Checklist entry inside a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskItem {
+    pub id: String,
+    pub task_id: String,
+    pub title: String,
+    pub completed: bool,
+    pub position: i64,   // 0-based order within the task
+    pub created_at: String,
+}
+
+/// Field tasks can be sorted by in a query
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TaskSortField {
+    #[serde(rename = "date")]
+    Date,          // date, then start time
+    #[serde(rename = "title")]
+    Title,
+    #[serde(rename = "category")]
+    Category,
+    #[serde(rename = "createdAt")]
+    CreatedAt,
+    #[serde(rename = "priority")]
+    Priority,      // lowest first when ascending
+}
+
+/// Sort direction for a task query
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SortDirection {
+    #[serde(rename = "asc")]
+    Asc,
+    #[serde(rename = "desc")]
+    Desc,
+}
+
+/// Filters, sorting and paging for `query_tasks`; every filter is optional
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskQuery {
+    pub user_id: String,
+    pub start_date: Option<String>,           // YYYY-MM-DD, inclusive
+    pub end_date: Option<String>,             // YYYY-MM-DD, inclusive; defaults to start_date
+    pub completed: Option<bool>,
+    pub categories: Option<Vec<TaskCategory>>,
+    pub priorities: Option<Vec<TaskPriority>>,
+    pub tags: Option<Vec<String>>,            // tasks with any of these tags (case-insensitive)
+    pub search: Option<String>,               // case-insensitive match on the title
+    pub sort_by: Option<TaskSortField>,       // defaults to date
+    pub sort_direction: Option<SortDirection>, // defaults to ascending
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// One page of a task query with the number of matches across all pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPage {
+    pub tasks: Vec<Task>,
+    pub total: i64,
+}
+
+/// Stored state of one occurrence of a recurring task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOccurrence {
+    pub id: String,
+    pub task_id: String,
+    pub occurrence_date: String,      // date the rule generated (YYYY-MM-DD)
+    pub completed: bool,
+    pub skipped: bool,
+    pub date: Option<String>,         // moved-to date, None keeps the original
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
+/// Estimated vs. logged focus time for one task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEstimate {
+    pub task_id: String,
+    pub title: String,
+    pub category: TaskCategory,
+    pub date: String,
+    pub completed: bool,
+    pub estimated_seconds: i64,
+    pub actual_seconds: i64,
+    pub difference_seconds: i64,   // actual - estimated; positive means it took longer
+    pub accuracy_ratio: f64,       // actual / estimated
+}
+
+/// Estimated vs. logged focus time summed over a task category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryEstimate {
+    pub category: TaskCategory,
+    pub task_count: i64,
+    pub estimated_seconds: i64,
+    pub actual_seconds: i64,
+    pub difference_seconds: i64,
+    pub accuracy_ratio: f64,
+}
+
+/// Estimate calibration report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateReport {
+    pub tasks: Vec<TaskEstimate>,
+    pub categories: Vec<CategoryEstimate>,
+}
+
+/// Why a task's date changed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DateChangeReason {
+    #[serde(rename = "rollover")]
+    Rollover,      // carried over to today while unfinished
+    #[serde(rename = "reschedule")]
+    Reschedule,    // date edited by hand
+}
+
+impl DateChangeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateChangeReason::Rollover => "rollover",
+            DateChangeReason::Reschedule => "reschedule",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "rollover" => Some(DateChangeReason::Rollover),
+            "reschedule" => Some(DateChangeReason::Reschedule),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded move of a task to another date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDateChange {
+    pub id: String,
+    pub task_id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub reason: DateChangeReason,
+    pub changed_at: String,
+}
+
+/// How far one task has been pushed back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPostponement {
+    pub task_id: String,
+    pub title: String,
+    pub category: TaskCategory,
+    pub original_date: String,
+    pub date: String,
+    pub completed: bool,
+    pub times_postponed: i64,
+    pub days_postponed: i64,   // current date - original date
+}
+
+/// How often work planned in a period was postponed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostponementReport {
+    pub task_count: i64,             // one-off tasks originally planned in the period
+    pub postponed_task_count: i64,
+    pub postponement_count: i64,     // moves to a later date, rollovers included
+    pub rollover_count: i64,
+    pub postponement_rate: f64,      // postponed_task_count / task_count
+    pub average_days_postponed: f64, // over postponed tasks
+    pub tasks: Vec<TaskPostponement>, // postponed tasks, most postponed first
+}
+
+/// Kind of change recorded in the task event log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TaskEventType {
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "updated")]
+    Updated,
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "reopened")]
+    Reopened,
+    #[serde(rename = "deleted")]
+    Deleted,
+}
+
+impl TaskEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskEventType::Created => "created",
+            TaskEventType::Updated => "updated",
+            TaskEventType::Completed => "completed",
+            TaskEventType::Reopened => "reopened",
+            TaskEventType::Deleted => "deleted",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "created" => Some(TaskEventType::Created),
+            "updated" => Some(TaskEventType::Updated),
+            "completed" => Some(TaskEventType::Completed),
+            "reopened" => Some(TaskEventType::Reopened),
+            "deleted" => Some(TaskEventType::Deleted),
+            _ => None,
+        }
+    }
+}
+
+/// One entry of the task event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEvent {
+    pub id: String,
+    pub task_id: String,
+    pub user_id: String,
+    pub occurrence_date: Option<String>,   // set for events on one occurrence of a recurring task
+    pub event_type: TaskEventType,
+    pub title: String,                     // task title at the time
+    pub occurred_at: String,               // RFC3339
+}
+
+/// Number of tasks completed on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTaskCompletions {
+    pub date: String,   // YYYY-MM-DD
+    pub tasks_completed: i64,
 }
@@ -0,0 +1,77 @@
+use crate::commands::reminder::deliver_due_reminders;
+use crate::db::Database;
+use crate::models::{TaskCategory, TaskReminder};
+use chrono::{DateTime, Utc};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Event emitted to the frontend for every reminder that fires
+pub const TASK_REMINDER_EVENT: &str = "task-reminder";
+
+/// How often the scheduler looks for due reminders
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Start the background thread that fires task reminders: each due reminder
+/// is recorded as delivered, emitted as a `task-reminder` event and shown as
+/// a desktop notification
+pub fn start_reminder_scheduler(app: AppHandle) {
+    thread::spawn(move || loop {
+        let now = Utc::now();
+        let due = {
+            let db = app.state::<Database>();
+            // A poisoned lock skips this tick; the connection is not trusted
+            let result = match db.conn.lock() {
+                Ok(conn) => deliver_due_reminders(&conn, now),
+                Err(e) => Err(format!("Could not lock the database: {}", e)),
+            };
+            result
+        };
+
+        match due {
+            Ok(reminders) => {
+                for reminder in reminders {
+                    notify(&app, &reminder, now);
+                }
+            }
+            Err(e) => log::warn!("Failed to check task reminders: {}", e),
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    });
+}
+
+fn notify(app: &AppHandle, reminder: &TaskReminder, now: DateTime<Utc>) {
+    if let Err(e) = app.emit(TASK_REMINDER_EVENT, reminder) {
+        log::warn!("Failed to emit task reminder: {}", e);
+    }
+
+    let result = app
+        .notification()
+        .builder()
+        .title(&reminder.title)
+        .body(reminder_body(reminder, now))
+        .show();
+    if let Err(e) = result {
+        log::warn!("Failed to show task reminder notification: {}", e);
+    }
+}
+
+/// Notification text such as "Starts in 10 min" or "Was due 5 min ago"
+fn reminder_body(reminder: &TaskReminder, now: DateTime<Utc>) -> String {
+    let (upcoming, past) = match reminder.category {
+        TaskCategory::Event => ("Starts", "Started"),
+        _ => ("Due", "Was due"),
+    };
+
+    let minutes = DateTime::parse_from_rfc3339(&reminder.due_at)
+        .map(|due| (due.with_timezone(&Utc) - now).num_minutes())
+        .unwrap_or(0);
+
+    match minutes {
+        0 => format!("{} now", upcoming),
+        m if m > 0 => format!("{} in {} min", upcoming, m),
+        m => format!("{} {} min ago", past, -m),
+    }
+}